@@ -0,0 +1,135 @@
+use std::{
+    error::Error,
+    fmt,
+    path::{Component, Path},
+};
+
+use smash_arc::{ArcFile, ArcLookup, LookupError, Region};
+
+use crate::{EffData, EffDiff, EffSource};
+
+/// An error that can occur when reading an EFF file from a data.arc.
+#[derive(Debug)]
+pub enum ArcError {
+    /// The path of the modded file could not be mapped to a path in the data.arc.
+    UnknownPath(String),
+
+    /// The data.arc could not be opened.
+    Open(Box<dyn Error + Send + Sync>),
+
+    /// The file could not be found or read from the data.arc.
+    Lookup(LookupError),
+
+    /// An EFF file could not be parsed.
+    Read(binrw::Error),
+}
+
+impl fmt::Display for ArcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownPath(path) => write!(f, "could not locate `{path}` in the data.arc"),
+            Self::Open(error) => write!(f, "failed to open the data.arc: {error}"),
+            Self::Lookup(error) => write!(f, "failed to read from the data.arc: {error}"),
+            Self::Read(error) => write!(f, "failed to read the EFF file: {error}"),
+        }
+    }
+}
+
+impl Error for ArcError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::UnknownPath(_) => None,
+            Self::Open(error) => Some(error.as_ref()),
+            Self::Lookup(error) => Some(error),
+            Self::Read(error) => Some(error),
+        }
+    }
+}
+
+impl From<LookupError> for ArcError {
+    fn from(value: LookupError) -> Self {
+        Self::Lookup(value)
+    }
+}
+
+impl From<binrw::Error> for ArcError {
+    fn from(value: binrw::Error) -> Self {
+        Self::Read(value)
+    }
+}
+
+/// Returns the path in the data.arc corresponding to the given file path.
+///
+/// Mod files mirror the layout of the data.arc, so the path is taken from the last `effect` directory onward.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// let path = eff_data::arc_path("mods/my_mod/effect/fighter/mario/ef_mario.eff");
+/// assert_eq!(path.as_deref(), Some("effect/fighter/mario/ef_mario.eff"));
+/// ```
+pub fn arc_path<P: AsRef<Path>>(path: P) -> Option<String> {
+    let components = path
+        .as_ref()
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .flat_map(|name| name.split('\\'))
+        .collect::<Vec<_>>();
+    let start = components.iter().rposition(|name| *name == "effect")?;
+
+    Some(components[start..].join("/"))
+}
+
+/// A data.arc as a source of EFF files, with paths given as paths in the data.arc.
+#[derive(Debug, Clone, Copy)]
+pub struct ArcSource<'a, A: ArcLookup> {
+    arc: &'a A,
+    region: Region,
+}
+
+impl<'a, A: ArcLookup> ArcSource<'a, A> {
+    /// Constructs a new source reading from the given data.arc, using the files of the given region.
+    pub fn new(arc: &'a A, region: Region) -> Self {
+        Self { arc, region }
+    }
+}
+
+impl<A: ArcLookup> EffSource for ArcSource<'_, A> {
+    type Error = ArcError;
+
+    fn read_bytes(&mut self, path: &Path) -> Result<Vec<u8>, ArcError> {
+        let internal_path = path
+            .to_str()
+            .ok_or_else(|| ArcError::UnknownPath(path.display().to_string()))?;
+
+        Ok(self.arc.get_file_contents(internal_path, self.region)?)
+    }
+}
+
+impl EffData {
+    /// Reads the data from the file at the given path in the data.arc, using the file of the given region.
+    pub fn from_arc<A: ArcLookup>(arc: &A, path: &str, region: Region) -> Result<Self, ArcError> {
+        Self::from_source(&mut ArcSource::new(arc, region), path)
+    }
+
+    /// Computes the differences from the vanilla counterpart of the file at `path` in the data.arc at `arc_path`, using the file of the given region.
+    ///
+    /// The vanilla file is located through [`arc_path`], so `path` must be inside a directory structure mirroring the data.arc.
+    pub fn diff_against_vanilla<P: AsRef<Path>, Q: AsRef<Path>>(
+        path: P,
+        arc_path: Q,
+        region: Region,
+    ) -> Result<EffDiff, ArcError> {
+        let modded = Self::from_file(&path)?;
+        let internal_path = self::arc_path(&path)
+            .ok_or_else(|| ArcError::UnknownPath(path.as_ref().display().to_string()))?;
+        let arc = ArcFile::open(arc_path).map_err(|error| ArcError::Open(Box::new(error)))?;
+
+        Ok(Self::from_arc(&arc, &internal_path, region)?.diff(&modded))
+    }
+}
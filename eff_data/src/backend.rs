@@ -0,0 +1,145 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, Cursor, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use binrw::BinResult;
+
+use crate::EffData;
+
+/// A backend which EFF files can be read from, such as a directory, an archive, or an in-memory store.
+pub trait EffSource {
+    /// The type of error returned when a file cannot be read or parsed.
+    type Error: From<binrw::Error>;
+
+    /// Reads the contents of the file at the given path.
+    fn read_bytes(&mut self, path: &Path) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// A backend which EFF files can be written to, such as a directory, an archive, or an in-memory store.
+pub trait EffSink {
+    /// The type of error returned when a file cannot be written.
+    type Error: From<binrw::Error>;
+
+    /// Writes the given contents to the file at the given path, replacing any existing file.
+    fn write_bytes(&mut self, path: &Path, bytes: Vec<u8>) -> Result<(), Self::Error>;
+}
+
+/// The file system, with paths resolved relative to the current working directory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystem;
+
+impl EffSource for FileSystem {
+    type Error = binrw::Error;
+
+    fn read_bytes(&mut self, path: &Path) -> BinResult<Vec<u8>> {
+        Ok(fs::read(path)?)
+    }
+}
+
+impl EffSink for FileSystem {
+    type Error = binrw::Error;
+
+    fn write_bytes(&mut self, path: &Path, bytes: Vec<u8>) -> BinResult<()> {
+        Ok(fs::write(path, bytes)?)
+    }
+}
+
+/// An in-memory collection of files, keyed by path.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_data::{EffData, MemoryStore};
+///
+/// let data = EffData::new();
+/// let mut store = MemoryStore::new();
+///
+/// data.write_to_sink(&mut store, "ef_mario.eff").unwrap();
+///
+/// assert_eq!(EffData::from_source(&mut store, "ef_mario.eff").unwrap(), data);
+/// assert!(EffData::from_source(&mut store, "ef_luigi.eff").is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MemoryStore {
+    files: BTreeMap<PathBuf, Vec<u8>>,
+}
+
+impl MemoryStore {
+    /// Constructs a new, empty instance of [`MemoryStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a file with the given contents, returning the previous contents if the file existed.
+    pub fn insert<P: Into<PathBuf>>(&mut self, path: P, bytes: Vec<u8>) -> Option<Vec<u8>> {
+        self.files.insert(path.into(), bytes)
+    }
+
+    /// Returns the contents of the file at the given path.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&[u8]> {
+        self.files.get(path.as_ref()).map(Vec::as_slice)
+    }
+
+    /// Removes the file at the given path, returning its contents if it existed.
+    pub fn remove<P: AsRef<Path>>(&mut self, path: P) -> Option<Vec<u8>> {
+        self.files.remove(path.as_ref())
+    }
+
+    /// Returns an iterator over the paths of the stored files, in sorted order.
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+}
+
+impl EffSource for MemoryStore {
+    type Error = binrw::Error;
+
+    fn read_bytes(&mut self, path: &Path) -> BinResult<Vec<u8>> {
+        self.files.get(path).cloned().ok_or_else(|| {
+            io::Error::new(
+                ErrorKind::NotFound,
+                format!("`{}` is not in the store", path.display()),
+            )
+            .into()
+        })
+    }
+}
+
+impl EffSink for MemoryStore {
+    type Error = binrw::Error;
+
+    fn write_bytes(&mut self, path: &Path, bytes: Vec<u8>) -> BinResult<()> {
+        self.files.insert(path.to_path_buf(), bytes);
+
+        Ok(())
+    }
+}
+
+impl EffData {
+    /// Reads the data from the file at the given path in the given source.
+    pub fn from_source<S: EffSource, P: AsRef<Path>>(
+        source: &mut S,
+        path: P,
+    ) -> Result<Self, S::Error> {
+        let bytes = source.read_bytes(path.as_ref())?;
+
+        Ok(Self::read(&mut Cursor::new(bytes))?)
+    }
+
+    /// Writes the data to the file at the given path in the given sink.
+    pub fn write_to_sink<S: EffSink, P: AsRef<Path>>(
+        &self,
+        sink: &mut S,
+        path: P,
+    ) -> Result<(), S::Error> {
+        let mut cursor = Cursor::new(Vec::new());
+
+        self.write(&mut cursor)?;
+        sink.write_bytes(path.as_ref(), cursor.into_inner())
+    }
+}
@@ -0,0 +1,125 @@
+use crate::EffData;
+
+/// A columnar copy of the numeric fields of an [`EffData`], with one contiguous array per field.
+///
+/// Effect handle columns are in effect handle order, and effect group element columns list the elements of every effect handle in the same order.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_data::{EffColumns, EffData};
+///
+/// let data = EffData::new();
+/// let columns = EffColumns::from(&data);
+///
+/// assert_eq!(columns.effect_handle_count(), 0);
+/// assert_eq!(columns.flag_count(18), 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EffColumns {
+    /// Flags of each effect handle, with each flag stored at its bit index.
+    pub flag_bits: Vec<u32>,
+
+    /// Positive index to the emitter set of each effect handle.
+    pub emitter_set_handles: Vec<i32>,
+
+    /// Index of the effect model entry used by each effect handle, or `-1` if there is none.
+    pub effect_model_indices: Vec<i32>,
+
+    /// Index of the first element of each effect handle in the effect group element columns.
+    pub effect_group_starts: Vec<u32>,
+
+    /// Number of effect group elements of each effect handle.
+    pub effect_group_counts: Vec<u32>,
+
+    /// Index of the effect handle containing each effect group element.
+    pub element_effect_handle_indices: Vec<u32>,
+
+    /// Frame to request the emitter set on for each effect group element.
+    pub element_start_frames: Vec<i16>,
+
+    /// Positive index to the emitter set of each effect group element.
+    pub element_emitter_set_handles: Vec<i16>,
+}
+
+impl EffColumns {
+    /// Returns the number of effect handles.
+    pub fn effect_handle_count(&self) -> usize {
+        self.flag_bits.len()
+    }
+
+    /// Returns the number of effect group elements.
+    pub fn effect_group_element_count(&self) -> usize {
+        self.element_start_frames.len()
+    }
+
+    /// Returns the number of effect handles with the flag at the given bit index set.
+    pub fn flag_count(&self, bit: u32) -> usize {
+        self.flag_bits
+            .iter()
+            .filter(|bits| bit < u32::BITS && *bits & (1 << bit) != 0)
+            .count()
+    }
+}
+
+impl From<&EffData> for EffColumns {
+    fn from(value: &EffData) -> Self {
+        let element_count = value
+            .effect_handles
+            .iter()
+            .map(|handle| handle.effect_group.len())
+            .sum();
+        let mut columns = Self {
+            flag_bits: Vec::with_capacity(value.effect_handles.len()),
+            emitter_set_handles: Vec::with_capacity(value.effect_handles.len()),
+            effect_model_indices: Vec::with_capacity(value.effect_handles.len()),
+            effect_group_starts: Vec::with_capacity(value.effect_handles.len()),
+            effect_group_counts: Vec::with_capacity(value.effect_handles.len()),
+            element_effect_handle_indices: Vec::with_capacity(element_count),
+            element_start_frames: Vec::with_capacity(element_count),
+            element_emitter_set_handles: Vec::with_capacity(element_count),
+        };
+
+        for (i, handle) in value.effect_handles.iter().enumerate() {
+            columns.flag_bits.push(handle.flags.to_bits());
+            columns.emitter_set_handles.push(handle.emitter_set_handle);
+            columns.effect_model_indices.push(
+                value
+                    .effect_model_entries
+                    .iter()
+                    .position(|model| {
+                        !handle.effect_model_name.is_empty()
+                            && model.name == handle.effect_model_name
+                    })
+                    .map_or(-1, |index| index as i32),
+            );
+            columns
+                .effect_group_starts
+                .push(columns.element_start_frames.len() as u32);
+            columns
+                .effect_group_counts
+                .push(handle.effect_group.len() as u32);
+
+            for element in &handle.effect_group {
+                columns.element_effect_handle_indices.push(i as u32);
+                columns
+                    .element_start_frames
+                    .push(element.emitter_set_start_frame);
+                columns
+                    .element_emitter_set_handles
+                    .push(element.emitter_set_handle);
+            }
+        }
+
+        columns
+    }
+}
+
+impl EffData {
+    /// Builds a columnar copy of the numeric fields of the data.
+    pub fn to_columns(&self) -> EffColumns {
+        self.into()
+    }
+}
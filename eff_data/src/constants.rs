@@ -0,0 +1,79 @@
+use std::io::{self, Write};
+
+use crate::{hash40, EffData};
+
+/// The programming language to generate effect handle constants for.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ConstantsLanguage {
+    /// Rust source code with `pub const` items.
+    Rust,
+
+    /// A C header with `static const` variables.
+    C,
+}
+
+impl EffData {
+    /// Writes source code defining the index and Hash40 of every effect handle to the given writer.
+    ///
+    /// For each effect handle, a constant named after the handle holds its index and a constant with a `_HASH` suffix holds the Hash40 of its lowercase name.
+    /// Characters which are not valid in identifiers are replaced with underscores.
+    pub fn write_constants<W: Write>(
+        &self,
+        writer: &mut W,
+        language: ConstantsLanguage,
+    ) -> io::Result<()> {
+        match language {
+            ConstantsLanguage::Rust => {
+                writeln!(writer, "// Generated by eff_data. Do not edit.")?;
+            }
+            ConstantsLanguage::C => {
+                writeln!(writer, "// Generated by eff_data. Do not edit.")?;
+                writeln!(writer, "#pragma once")?;
+                writeln!(writer)?;
+                writeln!(writer, "#include <stdint.h>")?;
+            }
+        }
+
+        for (i, handle) in self.effect_handles.iter().enumerate() {
+            let identifier = constant_identifier(&handle.name);
+            let hash = hash40(&handle.name.to_lowercase());
+
+            writeln!(writer)?;
+
+            match language {
+                ConstantsLanguage::Rust => {
+                    writeln!(writer, "pub const {identifier}: usize = {i};")?;
+                    writeln!(writer, "pub const {identifier}_HASH: u64 = 0x{hash:010X};")?;
+                }
+                ConstantsLanguage::C => {
+                    writeln!(writer, "static const uint32_t {identifier} = {i};")?;
+                    writeln!(
+                        writer,
+                        "static const uint64_t {identifier}_HASH = 0x{hash:010X}ULL;"
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn constant_identifier(name: &str) -> String {
+    let identifier = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+
+    if identifier.starts_with(|c: char| !c.is_ascii_alphabetic()) {
+        format!("_{identifier}")
+    } else {
+        identifier
+    }
+}
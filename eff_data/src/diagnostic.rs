@@ -0,0 +1,72 @@
+use std::{
+    error::Error,
+    fmt,
+    io::{Read, Seek},
+    path::Path,
+};
+
+use eff_lib::EffFile;
+
+use crate::{DataError, EffData};
+
+/// A problem found while reading an EFF file.
+#[derive(Debug)]
+pub enum Diagnostic {
+    /// The file could not be parsed, so no data could be recovered.
+    Read(binrw::Error),
+
+    /// Part of the data is invalid, so a placeholder was substituted for it.
+    Data(DataError),
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(error) => write!(f, "failed to read the EFF file: {error}"),
+            Self::Data(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Read(error) => Some(error),
+            Self::Data(error) => Some(error),
+        }
+    }
+}
+
+impl EffData {
+    /// Reads as much of the data as possible from the given reader, returning it with every problem found.
+    ///
+    /// Invalid names are decoded lossily, and missing effect models and effect groups are left empty.
+    /// No data is returned only if the tables could not be parsed at all.
+    pub fn read_with_diagnostics<R: Read + Seek>(
+        reader: &mut R,
+    ) -> (Option<Self>, Vec<Diagnostic>) {
+        match EffFile::read(reader) {
+            Ok(file) => Self::from_eff_file_with_diagnostics(&file),
+            Err(error) => (None, vec![Diagnostic::Read(error)]),
+        }
+    }
+
+    /// Reads as much of the data as possible from the given file path, returning it with every problem found.
+    pub fn from_file_with_diagnostics<P: AsRef<Path>>(path: P) -> (Option<Self>, Vec<Diagnostic>) {
+        match EffFile::from_file(path) {
+            Ok(file) => Self::from_eff_file_with_diagnostics(&file),
+            Err(error) => (None, vec![Diagnostic::Read(error)]),
+        }
+    }
+
+    fn from_eff_file_with_diagnostics(file: &EffFile) -> (Option<Self>, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::new();
+        let data = Self::convert_eff_file(file, |error| {
+            diagnostics.push(Diagnostic::Data(error));
+
+            Ok(())
+        });
+
+        (data.ok(), diagnostics)
+    }
+}
@@ -0,0 +1,381 @@
+use crate::{EffData, EffectHandleData, EffectModelEntryData};
+
+/// A difference between two versions of a named entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change<T> {
+    /// The entry only exists in the new version.
+    Added(T),
+
+    /// The entry only exists in the old version.
+    Removed(T),
+
+    /// The entry exists in both versions with different contents.
+    Modified { old: T, new: T },
+}
+
+impl<T> Change<T> {
+    /// Returns the most recent version of the entry.
+    pub fn current(&self) -> &T {
+        match self {
+            Self::Added(value) | Self::Removed(value) => value,
+            Self::Modified { new, .. } => new,
+        }
+    }
+}
+
+/// The differences between two [`EffData`] instances.
+///
+/// Effect handles and effect model entries are matched by name.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EffDiff {
+    /// Collection of changed effect handles.
+    pub effect_handles: Vec<Change<EffectHandleData>>,
+
+    /// Collection of changed effect model entries.
+    pub effect_model_entries: Vec<Change<EffectModelEntryData>>,
+
+    /// Whether the contained file resource differs.
+    #[cfg(feature = "resource")]
+    pub resource_changed: bool,
+}
+
+impl EffDiff {
+    /// Returns `true` if no differences were found, and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        let is_empty = self.effect_handles.is_empty() && self.effect_model_entries.is_empty();
+
+        #[cfg(feature = "resource")]
+        let is_empty = is_empty && !self.resource_changed;
+
+        is_empty
+    }
+}
+
+impl EffData {
+    /// Computes the differences from `self` to `other`, treating `self` as the old version.
+    pub fn diff(&self, other: &EffData) -> EffDiff {
+        EffDiff {
+            effect_handles: diff_named(&self.effect_handles, &other.effect_handles, |handle| {
+                &handle.name
+            }),
+            effect_model_entries: diff_named(
+                &self.effect_model_entries,
+                &other.effect_model_entries,
+                |model| &model.name,
+            ),
+            #[cfg(feature = "resource")]
+            resource_changed: self.resource_data != other.resource_data,
+        }
+    }
+
+    /// Returns `true` if both instances describe the same effects, and `false` otherwise.
+    ///
+    /// Unlike `==`, the order of the effect model entries and any user annotations are ignored.
+    /// Effect group elements are compared per effect handle, so how they were packed in the file does not matter.
+    pub fn semantically_eq(&self, other: &EffData) -> bool {
+        let effect_handles_eq = self.effect_handles.len() == other.effect_handles.len()
+            && self
+                .effect_handles
+                .iter()
+                .zip(other.effect_handles.iter())
+                .all(|(handle, other_handle)| {
+                    handle.name == other_handle.name
+                        && handle.flags == other_handle.flags
+                        && handle.emitter_set_handle == other_handle.emitter_set_handle
+                        && handle.effect_model_name == other_handle.effect_model_name
+                        && handle.effect_group == other_handle.effect_group
+                });
+        let effect_model_entries_eq =
+            sorted_effect_model_entries(self) == sorted_effect_model_entries(other);
+
+        #[cfg(feature = "resource")]
+        let resource_eq = self.resource_data == other.resource_data;
+        #[cfg(not(feature = "resource"))]
+        let resource_eq = true;
+
+        effect_handles_eq && effect_model_entries_eq && resource_eq
+    }
+
+    /// Returns `true` if both instances describe the same effects regardless of order, and `false` otherwise.
+    ///
+    /// Like [`semantically_eq`](Self::semantically_eq), but the order of the effect handles is ignored as well.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for name in ["EFF_SMOKE", "EFF_SPARK"] {
+    ///     data.add_effect_handle(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// }
+    ///
+    /// let mut reversed = data.clone();
+    /// reversed.effect_handles.reverse();
+    ///
+    /// assert!(!data.semantically_eq(&reversed));
+    /// assert!(data.is_equivalent(&reversed));
+    /// ```
+    pub fn is_equivalent(&self, other: &EffData) -> bool {
+        let effect_handles_eq = sorted_effect_handles(self) == sorted_effect_handles(other);
+        let effect_model_entries_eq =
+            sorted_effect_model_entries(self) == sorted_effect_model_entries(other);
+
+        #[cfg(feature = "resource")]
+        let resource_eq = self.resource_data == other.resource_data;
+        #[cfg(not(feature = "resource"))]
+        let resource_eq = true;
+
+        effect_handles_eq && effect_model_entries_eq && resource_eq
+    }
+}
+
+type HandleKey<'a> = (&'a str, u32, i32, &'a str, Vec<(i16, i16, &'a str)>);
+
+fn sorted_effect_handles(data: &EffData) -> Vec<HandleKey<'_>> {
+    let mut handles = data
+        .effect_handles
+        .iter()
+        .map(|handle| {
+            (
+                handle.name.as_str(),
+                handle.flags.to_bits(),
+                handle.emitter_set_handle,
+                handle.effect_model_name.as_str(),
+                handle
+                    .effect_group
+                    .iter()
+                    .map(|element| {
+                        (
+                            element.emitter_set_start_frame,
+                            element.emitter_set_handle,
+                            element.parent_joint_name.as_str(),
+                        )
+                    })
+                    .collect(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    handles.sort_unstable();
+    handles
+}
+
+fn sorted_effect_model_entries(data: &EffData) -> Vec<(&str, i8)> {
+    let mut models = data
+        .effect_model_entries
+        .iter()
+        .map(|model| (model.name.as_str(), i8::from(model.unk)))
+        .collect::<Vec<_>>();
+
+    models.sort_unstable();
+    models
+}
+
+fn diff_named<T, F>(old: &[T], new: &[T], name: F) -> Vec<Change<T>>
+where
+    T: Clone + PartialEq,
+    F: Fn(&T) -> &String,
+{
+    let mut changes = Vec::new();
+
+    for old_value in old {
+        match new
+            .iter()
+            .find(|new_value| name(new_value) == name(old_value))
+        {
+            Some(new_value) if new_value != old_value => changes.push(Change::Modified {
+                old: old_value.clone(),
+                new: new_value.clone(),
+            }),
+            Some(_) => {}
+            None => changes.push(Change::Removed(old_value.clone())),
+        }
+    }
+
+    for new_value in new {
+        if !old
+            .iter()
+            .any(|old_value| name(old_value) == name(new_value))
+        {
+            changes.push(Change::Added(new_value.clone()));
+        }
+    }
+
+    changes
+}
+
+/// The entries touched by two modified versions of the same [`EffData`], and where they conflict.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConflictReport {
+    /// Names of the effect handles changed by the first version.
+    pub first_effect_handles: Vec<String>,
+
+    /// Names of the effect handles changed by the second version.
+    pub second_effect_handles: Vec<String>,
+
+    /// Names of the effect handles changed differently by both versions.
+    pub conflicting_effect_handles: Vec<String>,
+
+    /// Names of the effect model entries changed by the first version.
+    pub first_effect_model_entries: Vec<String>,
+
+    /// Names of the effect model entries changed by the second version.
+    pub second_effect_model_entries: Vec<String>,
+
+    /// Names of the effect model entries changed differently by both versions.
+    pub conflicting_effect_model_entries: Vec<String>,
+
+    /// Whether both versions changed the contained file resource differently.
+    #[cfg(feature = "resource")]
+    pub resource_conflict: bool,
+}
+
+impl ConflictReport {
+    /// Returns `true` if the changes of both versions can be combined, and `false` otherwise.
+    pub fn is_mergeable(&self) -> bool {
+        let is_mergeable = self.conflicting_effect_handles.is_empty()
+            && self.conflicting_effect_model_entries.is_empty();
+
+        #[cfg(feature = "resource")]
+        let is_mergeable = is_mergeable && !self.resource_conflict;
+
+        is_mergeable
+    }
+}
+
+impl EffData {
+    /// Reports which entries of `base` were changed by `first` and `second`, and which of them conflict.
+    ///
+    /// An entry changed identically by both versions is not a conflict.
+    pub fn conflicts(base: &EffData, first: &EffData, second: &EffData) -> ConflictReport {
+        let first_diff = base.diff(first);
+        let second_diff = base.diff(second);
+        let (first_effect_handles, second_effect_handles, conflicting_effect_handles) =
+            conflicting_names(
+                &first_diff.effect_handles,
+                &second_diff.effect_handles,
+                |handle| &handle.name,
+            );
+        let (
+            first_effect_model_entries,
+            second_effect_model_entries,
+            conflicting_effect_model_entries,
+        ) = conflicting_names(
+            &first_diff.effect_model_entries,
+            &second_diff.effect_model_entries,
+            |model| &model.name,
+        );
+
+        ConflictReport {
+            first_effect_handles,
+            second_effect_handles,
+            conflicting_effect_handles,
+            first_effect_model_entries,
+            second_effect_model_entries,
+            conflicting_effect_model_entries,
+            #[cfg(feature = "resource")]
+            resource_conflict: first_diff.resource_changed
+                && second_diff.resource_changed
+                && first.resource_data != second.resource_data,
+        }
+    }
+
+    /// Combines the changes made to `base` by `first` and `second` into a new [`EffData`].
+    ///
+    /// Returns the [`ConflictReport`] instead if both versions changed the same entry differently.
+    pub fn three_way_merge(
+        base: &EffData,
+        first: &EffData,
+        second: &EffData,
+    ) -> Result<EffData, Box<ConflictReport>> {
+        let report = Self::conflicts(base, first, second);
+
+        if !report.is_mergeable() {
+            return Err(Box::new(report));
+        }
+
+        let mut merged = base.clone();
+
+        for diff in [base.diff(first), base.diff(second)] {
+            apply_changes(&mut merged.effect_handles, diff.effect_handles, |handle| {
+                &handle.name
+            });
+            apply_changes(
+                &mut merged.effect_model_entries,
+                diff.effect_model_entries,
+                |model| &model.name,
+            );
+        }
+
+        #[cfg(feature = "resource")]
+        if first.resource_data != base.resource_data {
+            merged.resource_data.clone_from(&first.resource_data);
+        } else {
+            merged.resource_data.clone_from(&second.resource_data);
+        }
+
+        Ok(merged)
+    }
+}
+
+fn conflicting_names<T, F>(
+    first: &[Change<T>],
+    second: &[Change<T>],
+    name: F,
+) -> (Vec<String>, Vec<String>, Vec<String>)
+where
+    T: PartialEq,
+    F: Fn(&T) -> &String,
+{
+    let conflicting = first
+        .iter()
+        .filter(|first_change| {
+            second.iter().any(|second_change| {
+                name(second_change.current()) == name(first_change.current())
+                    && second_change != *first_change
+            })
+        })
+        .map(|change| name(change.current()).clone())
+        .collect();
+
+    (
+        first
+            .iter()
+            .map(|change| name(change.current()).clone())
+            .collect(),
+        second
+            .iter()
+            .map(|change| name(change.current()).clone())
+            .collect(),
+        conflicting,
+    )
+}
+
+fn apply_changes<T, F>(values: &mut Vec<T>, changes: Vec<Change<T>>, name: F)
+where
+    F: Fn(&T) -> &String,
+{
+    for change in changes {
+        match change {
+            Change::Added(new) => {
+                if !values.iter().any(|value| name(value) == name(&new)) {
+                    values.push(new);
+                }
+            }
+            Change::Removed(old) => values.retain(|value| name(value) != name(&old)),
+            Change::Modified { new, .. } => {
+                if let Some(value) = values.iter_mut().find(|value| name(value) == name(&new)) {
+                    *value = new;
+                }
+            }
+        }
+    }
+}
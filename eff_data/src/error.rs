@@ -0,0 +1,230 @@
+use std::{error::Error, fmt, str::Utf8Error};
+
+/// A name table of an [`EffFile`](eff_lib::EffFile).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum NameKind {
+    EffectHandle,
+    EffectModel,
+    ParentJoint,
+}
+
+impl fmt::Display for NameKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EffectHandle => write!(f, "effect handle name"),
+            Self::EffectModel => write!(f, "effect model name"),
+            Self::ParentJoint => write!(f, "parent joint name"),
+        }
+    }
+}
+
+/// A table of entries of an [`EffFile`](eff_lib::EffFile).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TableKind {
+    EffectHandles,
+    EffectGroupElements,
+    EffectModelEntries,
+}
+
+impl fmt::Display for TableKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EffectHandles => write!(f, "effect handles"),
+            Self::EffectGroupElements => write!(f, "effect group elements"),
+            Self::EffectModelEntries => write!(f, "effect model entries"),
+        }
+    }
+}
+
+/// A kind of resource indexed by the emitters of a [`PtclData`](crate::PtclData).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ResourceKind {
+    Texture,
+    Shader,
+    Primitive,
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Texture => write!(f, "texture"),
+            Self::Shader => write!(f, "shader"),
+            Self::Primitive => write!(f, "primitive"),
+        }
+    }
+}
+
+/// An error that can occur when converting between an [`EffFile`](eff_lib::EffFile) and an [`EffData`](crate::EffData).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError {
+    /// A name is not valid UTF-8.
+    InvalidName {
+        table: NameKind,
+        index: usize,
+        error: Utf8Error,
+    },
+
+    /// An effect handle references an effect model entry which does not exist.
+    EffectModelOutOfRange {
+        effect_handle_index: usize,
+        effect_model_entry_handle: i32,
+    },
+
+    /// An effect handle references effect group elements which do not exist.
+    EffectGroupOutOfRange {
+        effect_handle_index: usize,
+        effect_group_element_start: i16,
+        effect_group_element_count: i16,
+    },
+
+    /// An effect handle references an effect model name without an effect model entry.
+    UnknownEffectModel {
+        effect_handle_name: String,
+        effect_model_name: String,
+    },
+
+    /// A table has more entries than the format can store.
+    TooManyEntries { table: TableKind, count: usize },
+
+    /// An emitter set cannot be removed because an effect handle still references it.
+    EmitterSetInUse {
+        emitter_set_handle: i32,
+        effect_handle_name: String,
+    },
+
+    /// No effect handle has the given name.
+    UnknownEffectHandle { name: String },
+
+    /// An effect handle with the given name already exists.
+    DuplicateEffectHandle { name: String },
+
+    /// An effect model entry with the given name already exists.
+    DuplicateEffectModel { name: String },
+
+    /// An effect handle references an emitter set which has no new index in a mapping.
+    UnmappedEmitterSet {
+        emitter_set_handle: i32,
+        effect_handle_name: String,
+    },
+
+    /// A shifted or remapped emitter set handle does not fit in the effect handle or effect group element referencing it.
+    EmitterSetHandleOutOfRange {
+        emitter_set_handle: i64,
+        effect_handle_name: String,
+    },
+
+    /// A resource at the given index differs between two resources whose emitter sets are combined.
+    ResourceMismatch { kind: ResourceKind, index: usize },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName {
+                table,
+                index,
+                error,
+            } => write!(f, "{table} {index} is not valid UTF-8: {error}"),
+            Self::EffectModelOutOfRange {
+                effect_handle_index,
+                effect_model_entry_handle,
+            } => write!(
+                f,
+                "effect handle {effect_handle_index} references missing effect model entry {effect_model_entry_handle}"
+            ),
+            Self::EffectGroupOutOfRange {
+                effect_handle_index,
+                effect_group_element_start,
+                effect_group_element_count,
+            } => write!(
+                f,
+                "effect handle {effect_handle_index} references {effect_group_element_count} effect group elements from {effect_group_element_start}, which do not exist"
+            ),
+            Self::UnknownEffectModel {
+                effect_handle_name,
+                effect_model_name,
+            } => write!(
+                f,
+                "effect handle `{effect_handle_name}` references effect model `{effect_model_name}` without an effect model entry"
+            ),
+            Self::TooManyEntries { table, count } => {
+                write!(f, "{count} {table} exceed the maximum of {}", i16::MAX)
+            }
+            Self::EmitterSetInUse {
+                emitter_set_handle,
+                effect_handle_name,
+            } => write!(
+                f,
+                "emitter set {emitter_set_handle} is still referenced by effect handle `{effect_handle_name}`"
+            ),
+            Self::UnknownEffectHandle { name } => {
+                write!(f, "there is no effect handle named `{name}`")
+            }
+            Self::DuplicateEffectHandle { name } => {
+                write!(f, "an effect handle named `{name}` already exists")
+            }
+            Self::DuplicateEffectModel { name } => {
+                write!(f, "an effect model entry named `{name}` already exists")
+            }
+            Self::UnmappedEmitterSet {
+                emitter_set_handle,
+                effect_handle_name,
+            } => write!(
+                f,
+                "emitter set {emitter_set_handle} referenced by effect handle `{effect_handle_name}` has no new index"
+            ),
+            Self::EmitterSetHandleOutOfRange {
+                emitter_set_handle,
+                effect_handle_name,
+            } => write!(
+                f,
+                "emitter set handle {emitter_set_handle} of effect handle `{effect_handle_name}` is out of range"
+            ),
+            Self::ResourceMismatch { kind, index } => write!(
+                f,
+                "{kind} {index} differs between the resources, and the {kind} indices of emitters cannot be rewritten"
+            ),
+        }
+    }
+}
+
+impl Error for DataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidName { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// An error that can occur when renaming an effect handle of an [`EffData`](crate::EffData).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum RenameError {
+    /// No effect handle has the given name.
+    Missing { name: String },
+
+    /// Another effect handle already has the new name.
+    Duplicate { name: String },
+}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { name } => write!(f, "there is no effect handle named `{name}`"),
+            Self::Duplicate { name } => {
+                write!(f, "an effect handle named `{name}` already exists")
+            }
+        }
+    }
+}
+
+impl Error for RenameError {}
+
+impl From<DataError> for binrw::Error {
+    fn from(value: DataError) -> Self {
+        Self::Custom {
+            pos: 0,
+            err: Box::new(value),
+        }
+    }
+}
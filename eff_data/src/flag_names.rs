@@ -0,0 +1,138 @@
+use std::{collections::BTreeMap, fmt};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{write_flag_names, EffectHandleDataFlags};
+
+/// A user-supplied mapping of flag bits to names, overriding the field names of [`EffectHandleDataFlags`].
+///
+/// Flags are identified by their bit index, so the flag stored in the field `unk_13` is bit 12.
+/// With the `serde` feature, the mapping is (de)serialized as a map whose keys are either bit indices or field names.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_data::FlagNames;
+///
+/// let mut names = FlagNames::new();
+/// names.insert(12, "my_flag");
+///
+/// assert_eq!(names.name("unk_13"), "my_flag");
+/// assert_eq!(names.name("unk_14"), "unk_14");
+/// assert_eq!(names.field_name("my_flag"), Some("unk_13"));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "BTreeMap<String, String>",
+        into = "BTreeMap<String, String>"
+    )
+)]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FlagNames {
+    names: BTreeMap<u32, String>,
+}
+
+impl FlagNames {
+    /// Constructs a new, empty instance of [`FlagNames`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if no flags have a user-supplied name, and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Sets the name of the flag at the given bit index, returning the previous name if one was set.
+    pub fn insert<S: Into<String>>(&mut self, bit: u32, name: S) -> Option<String> {
+        self.names.insert(bit, name.into())
+    }
+
+    /// Returns the name to use for the flag stored in the given field of [`EffectHandleDataFlags`].
+    ///
+    /// Fields without a user-supplied name keep their field name.
+    pub fn name<'a>(&'a self, field_name: &'a str) -> &'a str {
+        EffectHandleDataFlags::FIELDS
+            .iter()
+            .find(|(_, field)| *field == field_name)
+            .and_then(|(bit, _)| self.names.get(bit))
+            .map_or(field_name, String::as_str)
+    }
+
+    /// Returns the field of [`EffectHandleDataFlags`] storing the flag with the given name.
+    ///
+    /// Both user-supplied names and field names are accepted.
+    pub fn field_name(&self, name: &str) -> Option<&'static str> {
+        EffectHandleDataFlags::FIELDS
+            .iter()
+            .find(|(bit, _)| self.names.get(bit).is_some_and(|custom| custom == name))
+            .or_else(|| {
+                EffectHandleDataFlags::FIELDS
+                    .iter()
+                    .find(|(_, field)| *field == name)
+            })
+            .map(|(_, field)| *field)
+    }
+
+    /// Returns a value which displays the set flags as a comma-separated list of their names.
+    pub fn display<'a>(&'a self, flags: &'a EffectHandleDataFlags) -> impl fmt::Display + 'a {
+        DisplayFlags { names: self, flags }
+    }
+}
+
+impl TryFrom<BTreeMap<String, String>> for FlagNames {
+    type Error = String;
+
+    fn try_from(value: BTreeMap<String, String>) -> Result<Self, Self::Error> {
+        let mut names = Self::new();
+
+        for (key, name) in value {
+            let bit = match key.parse::<u32>() {
+                Ok(bit) if bit < u32::BITS => bit,
+                Ok(bit) => return Err(format!("flag bit {bit} is out of range")),
+                Err(_) => EffectHandleDataFlags::FIELDS
+                    .iter()
+                    .find(|(_, field)| *field == key)
+                    .map(|(bit, _)| *bit)
+                    .ok_or_else(|| format!("unknown flag `{key}`"))?,
+            };
+
+            names.insert(bit, name);
+        }
+
+        Ok(names)
+    }
+}
+
+impl From<FlagNames> for BTreeMap<String, String> {
+    fn from(value: FlagNames) -> Self {
+        value
+            .names
+            .into_iter()
+            .map(|(bit, name)| (bit.to_string(), name))
+            .collect()
+    }
+}
+
+struct DisplayFlags<'a> {
+    names: &'a FlagNames,
+    flags: &'a EffectHandleDataFlags,
+}
+
+impl fmt::Display for DisplayFlags<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flag_names(
+            f,
+            EffectHandleDataFlags::FIELDS
+                .iter()
+                .zip(self.flags.values())
+                .filter(|(_, value)| *value)
+                .map(|((_, field), _)| self.names.name(field)),
+        )
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::BTreeSet;
+
+use crate::{emitter_set_index, EffData};
+
+/// The pixel format of a texture.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TextureFormat {
+    R8,
+    R8G8,
+    R8G8B8A8,
+    R16G16B16A16,
+    R32G32B32A32,
+    Bc1,
+    Bc2,
+    Bc3,
+    Bc4,
+    Bc5,
+    Bc6,
+    Bc7,
+}
+
+impl TextureFormat {
+    /// Returns the width and height of a single block of pixels.
+    pub fn block_dimensions(self) -> (u32, u32) {
+        match self {
+            Self::R8 | Self::R8G8 | Self::R8G8B8A8 | Self::R16G16B16A16 | Self::R32G32B32A32 => {
+                (1, 1)
+            }
+            _ => (4, 4),
+        }
+    }
+
+    /// Returns the size in bytes of a single block of pixels.
+    pub fn bytes_per_block(self) -> usize {
+        match self {
+            Self::R8 => 1,
+            Self::R8G8 => 2,
+            Self::R8G8B8A8 => 4,
+            Self::R16G16B16A16 | Self::Bc1 | Self::Bc4 => 8,
+            Self::R32G32B32A32 | Self::Bc2 | Self::Bc3 | Self::Bc5 | Self::Bc6 | Self::Bc7 => 16,
+        }
+    }
+}
+
+/// The metadata of a texture needed to estimate its memory footprint.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TextureInfo {
+    /// Width of the texture in pixels.
+    pub width: u32,
+
+    /// Height of the texture in pixels.
+    pub height: u32,
+
+    /// Number of mipmaps, including the base level.
+    pub mip_count: u32,
+
+    /// Number of array layers.
+    pub array_count: u32,
+
+    /// Pixel format of the texture.
+    pub format: TextureFormat,
+}
+
+impl TextureInfo {
+    /// Returns the approximate size in bytes of the texture once loaded into GPU memory.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{TextureFormat, TextureInfo};
+    ///
+    /// let texture = TextureInfo {
+    ///     width: 64,
+    ///     height: 64,
+    ///     mip_count: 1,
+    ///     array_count: 1,
+    ///     format: TextureFormat::Bc1,
+    /// };
+    /// assert_eq!(texture.size(), 2048);
+    /// ```
+    pub fn size(&self) -> usize {
+        let (block_width, block_height) = self.format.block_dimensions();
+        let layer_size: usize = (0..self.mip_count.max(1))
+            .map(|mip| {
+                let width = (self.width >> mip).max(1);
+                let height = (self.height >> mip).max(1);
+
+                width.div_ceil(block_width) as usize
+                    * height.div_ceil(block_height) as usize
+                    * self.format.bytes_per_block()
+            })
+            .sum();
+
+        layer_size * self.array_count.max(1) as usize
+    }
+}
+
+/// The estimated GPU memory cost of the textures used by an effect file.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Footprint {
+    /// Approximate size in bytes of every texture in the resource.
+    pub total_size: usize,
+
+    /// Approximate size in bytes of the textures used by each effect handle, in handle order.
+    ///
+    /// Textures shared between effect handles are counted for each of them.
+    pub effect_handles: Vec<(String, usize)>,
+}
+
+impl EffData {
+    /// Estimates the GPU memory cost of the textures in the resource.
+    ///
+    /// `emitter_set_textures` contains the indices into `textures` used by each emitter set of the resource.
+    /// Textures are attributed to the effect handles referencing their emitter sets, either directly or through their effect group.
+    pub fn estimate_footprint(
+        &self,
+        textures: &[TextureInfo],
+        emitter_set_textures: &[Vec<usize>],
+    ) -> Footprint {
+        Footprint {
+            total_size: textures.iter().map(TextureInfo::size).sum(),
+            effect_handles: self
+                .effect_handles
+                .iter()
+                .map(|handle| {
+                    let texture_indices = std::iter::once(handle.emitter_set_handle)
+                        .chain(
+                            handle
+                                .effect_group
+                                .iter()
+                                .map(|element| element.emitter_set_handle as i32),
+                        )
+                        .filter_map(emitter_set_index)
+                        .filter_map(|i| emitter_set_textures.get(i))
+                        .flatten()
+                        .copied()
+                        .collect::<BTreeSet<_>>();
+                    let size = texture_indices
+                        .into_iter()
+                        .filter_map(|i| textures.get(i))
+                        .map(TextureInfo::size)
+                        .sum();
+
+                    (handle.name.clone(), size)
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+
+use petgraph::graph::{DiGraph, NodeIndex};
+
+use crate::{emitter_set_index, EffData};
+
+/// An entity in the relationship graph of an [`EffData`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GraphNode {
+    /// An effect handle, identified by name.
+    EffectHandle(String),
+
+    /// An emitter set in the file resource, identified by index.
+    EmitterSet(usize),
+
+    /// An effect model, identified by name.
+    EffectModel(String),
+
+    /// A joint which emitter sets are parented to, identified by name.
+    Joint(String),
+}
+
+/// A relationship in the relationship graph of an [`EffData`], directed away from the effect handle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphEdge {
+    /// The effect handle requests the emitter set directly.
+    EmitterSet,
+
+    /// The effect handle requests the emitter set through an effect group element on the given frame.
+    EffectGroupElement(i16),
+
+    /// The effect handle uses the effect model.
+    EffectModel,
+
+    /// The effect handle parents an emitter set to the joint.
+    ParentJoint,
+}
+
+impl EffData {
+    /// Builds a directed graph of the relationships between effect handles, emitter sets, effect models, and joints.
+    ///
+    /// Every entity is represented by a single node, so effect handles sharing an emitter set, effect model, or joint are connected through it.
+    /// Effect model entries which no effect handle uses are included as nodes without edges.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::EffData;
+    ///
+    /// let data = EffData::new();
+    /// let graph = data.to_graph();
+    ///
+    /// assert_eq!(graph.node_count(), 0);
+    /// ```
+    pub fn to_graph(&self) -> DiGraph<GraphNode, GraphEdge> {
+        let mut builder = GraphBuilder::default();
+
+        for model in &self.effect_model_entries {
+            builder.node(GraphNode::EffectModel(model.name.clone()));
+        }
+
+        for handle in &self.effect_handles {
+            let handle_node = builder.node(GraphNode::EffectHandle(handle.name.clone()));
+
+            if let Some(index) = emitter_set_index(handle.emitter_set_handle) {
+                builder.edge(
+                    handle_node,
+                    GraphNode::EmitterSet(index),
+                    GraphEdge::EmitterSet,
+                );
+            }
+
+            if !handle.effect_model_name.is_empty() {
+                builder.edge(
+                    handle_node,
+                    GraphNode::EffectModel(handle.effect_model_name.clone()),
+                    GraphEdge::EffectModel,
+                );
+            }
+
+            for element in &handle.effect_group {
+                if let Some(index) = emitter_set_index(element.emitter_set_handle.into()) {
+                    builder.edge(
+                        handle_node,
+                        GraphNode::EmitterSet(index),
+                        GraphEdge::EffectGroupElement(element.emitter_set_start_frame),
+                    );
+                }
+
+                if !element.parent_joint_name.is_empty() {
+                    builder.edge(
+                        handle_node,
+                        GraphNode::Joint(element.parent_joint_name.clone()),
+                        GraphEdge::ParentJoint,
+                    );
+                }
+            }
+        }
+
+        builder.graph
+    }
+}
+
+#[derive(Default)]
+struct GraphBuilder {
+    graph: DiGraph<GraphNode, GraphEdge>,
+    nodes: HashMap<GraphNode, NodeIndex>,
+}
+
+impl GraphBuilder {
+    fn node(&mut self, node: GraphNode) -> NodeIndex {
+        if let Some(index) = self.nodes.get(&node) {
+            return *index;
+        }
+
+        let index = self.graph.add_node(node.clone());
+
+        self.nodes.insert(node, index);
+
+        index
+    }
+
+    fn edge(&mut self, source: NodeIndex, target: GraphNode, edge: GraphEdge) {
+        let target = self.node(target);
+
+        self.graph.add_edge(source, target, edge);
+    }
+}
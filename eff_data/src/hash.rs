@@ -0,0 +1,47 @@
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+
+    while i < table.len() {
+        let mut crc = i as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+/// Computes the Hash40 of the given string, the 40-bit hash used by the game to identify names.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// assert_eq!(eff_data::hash40("sys_hit_normal"), 0x0E_9D765FDB);
+/// ```
+pub const fn hash40(string: &str) -> u64 {
+    let bytes = string.as_bytes();
+    let mut crc = !0u32;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        crc = CRC32_TABLE[((crc ^ bytes[i] as u32) & 0xFF) as usize] ^ (crc >> 8);
+        i += 1;
+    }
+
+    ((bytes.len() as u64) << 32) | !crc as u64
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use crate::{EffData, EffectHandleData, EffectModelEntryData};
+
+/// Maps the names of an [`EffData`] to the positions of its entries, for lookups without scanning.
+///
+/// The index is not updated when the data changes, so it should be rebuilt with [`rebuild`](Self::rebuild) after editing.
+/// Lookups which take the data check that the entry still has the requested name, and return `None` instead of a different entry if the index is out of date.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_data::{EffData, EffDataIndex, EffectHandleData};
+///
+/// let mut data = EffData::new();
+///
+/// for name in ["EFF_SMOKE", "EFF_SPARK"] {
+///     data.add_effect_handle(EffectHandleData {
+///         name: name.to_string(),
+///         ..Default::default()
+///     })
+///     .unwrap();
+/// }
+///
+/// let mut index = EffDataIndex::new(&data);
+///
+/// assert_eq!(index.handle_index("EFF_SPARK"), Some(1));
+///
+/// data.effect_handles.remove(0);
+///
+/// assert!(index.handle(&data, "EFF_SPARK").is_none());
+///
+/// index.rebuild(&data);
+///
+/// assert_eq!(index.handle(&data, "EFF_SPARK").unwrap().name, "EFF_SPARK");
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EffDataIndex {
+    effect_handles: HashMap<String, usize>,
+    effect_model_entries: HashMap<String, usize>,
+    parent_joints: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl EffDataIndex {
+    /// Builds an index of the given data.
+    pub fn new(data: &EffData) -> Self {
+        let mut index = Self::default();
+        index.rebuild(data);
+        index
+    }
+
+    /// Replaces the contents of the index with the names of the given data.
+    pub fn rebuild(&mut self, data: &EffData) {
+        self.effect_handles.clear();
+        self.effect_model_entries.clear();
+        self.parent_joints.clear();
+
+        for (i, handle) in data.effect_handles.iter().enumerate() {
+            self.effect_handles.entry(handle.name.clone()).or_insert(i);
+
+            for (j, element) in handle.effect_group.iter().enumerate() {
+                self.parent_joints
+                    .entry(element.parent_joint_name.clone())
+                    .or_default()
+                    .push((i, j));
+            }
+        }
+
+        for (i, model) in data.effect_model_entries.iter().enumerate() {
+            self.effect_model_entries
+                .entry(model.name.clone())
+                .or_insert(i);
+        }
+    }
+
+    /// Returns the position of the first effect handle with the given name.
+    pub fn handle_index(&self, name: &str) -> Option<usize> {
+        self.effect_handles.get(name).copied()
+    }
+
+    /// Returns the position of the first effect model entry with the given name.
+    pub fn model_entry_index(&self, name: &str) -> Option<usize> {
+        self.effect_model_entries.get(name).copied()
+    }
+
+    /// Returns the positions of the effect handles and of the effect group elements within them which use the given parent joint.
+    pub fn joint_indices(&self, name: &str) -> &[(usize, usize)] {
+        self.parent_joints.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the effect handle of `data` with the given name, if the index is up to date for it.
+    pub fn handle<'a>(&self, data: &'a EffData, name: &str) -> Option<&'a EffectHandleData> {
+        data.effect_handles
+            .get(self.handle_index(name)?)
+            .filter(|handle| handle.name == name)
+    }
+
+    /// Returns the effect model entry of `data` with the given name, if the index is up to date for it.
+    pub fn model_entry<'a>(
+        &self,
+        data: &'a EffData,
+        name: &str,
+    ) -> Option<&'a EffectModelEntryData> {
+        data.effect_model_entries
+            .get(self.model_entry_index(name)?)
+            .filter(|model| model.name == name)
+    }
+}
@@ -1,21 +1,80 @@
 //! # eff_data
 //!
 //! eff_data is a high-level library built off [eff_lib](https://crates.io/crates/eff_lib) for reading and writing EFF files from Super Smash Bros. Ultimate.
+//!
+//! Without the default `resource` feature, [`EffData`] does not hold the file resource, for tools which only need the tables.
+#[cfg(feature = "resource")]
+use std::{borrow::Cow, fs, mem};
 use std::{
-    fs,
-    io::{self, Read, Seek, Write},
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    io::{Read, Seek, Write},
     path::Path,
 };
 
 use binrw::BinResult;
-use eff_lib::{EffFile, EffectGroupElement, EffectHandle, EffectHandleFlags, EffectModelEntry};
+use eff_lib::{
+    CString, EffFile, EffectGroupElement, EffectHandle, EffectHandleFlags, EffectModelEntry,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "arc")]
+mod arc;
+mod backend;
+mod columns;
+mod constants;
+mod diagnostic;
+mod diff;
+mod error;
+mod flag_names;
+mod footprint;
+#[cfg(feature = "petgraph")]
+mod graph;
+mod hash;
+mod index;
+mod merge;
+#[cfg(feature = "serde")]
+mod meta;
+mod ptcl;
+mod resource;
+mod session;
+mod shared;
+mod stats;
+mod timeline;
+mod validate;
+
+#[cfg(feature = "arc")]
+pub use arc::{arc_path, ArcError, ArcSource};
+pub use backend::{EffSink, EffSource, FileSystem, MemoryStore};
+pub use columns::EffColumns;
+pub use constants::ConstantsLanguage;
+pub use diagnostic::Diagnostic;
+pub use diff::{Change, ConflictReport, EffDiff};
+pub use eff_lib::EffectModelEntryUnk;
+pub use error::{DataError, NameKind, RenameError, ResourceKind, TableKind};
+pub use flag_names::FlagNames;
+pub use footprint::{Footprint, TextureFormat, TextureInfo};
+#[cfg(feature = "petgraph")]
+pub use graph::{GraphEdge, GraphNode};
+pub use hash::hash40;
+pub use index::EffDataIndex;
+pub use merge::MergeStrategy;
+#[cfg(feature = "serde")]
+pub use meta::{EffMeta, EffectHandleMeta};
+pub use ptcl::{EmitterData, EmitterSetData, EmitterSetMapping, EmittersDepthFirst, PtclData};
+pub use resource::ResourceData;
+pub use session::{EffSession, ListenerId, SessionEvent};
+pub use shared::SharedEffData;
+pub use stats::{EffStats, EffectModelUsage, PtclStats};
+pub use timeline::TimelineEntry;
+pub use validate::UnknownJoint;
+
 /// The data associated with an [`EffFile`].
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct EffData {
     /// Collection of effect handles.
     pub effect_handles: Vec<EffectHandleData>,
@@ -23,20 +82,52 @@ pub struct EffData {
     /// Collection of effect model entries.
     pub effect_model_entries: Vec<EffectModelEntryData>,
 
-    /// Data buffer for the contained file resource.
+    /// The contained file resource, which is only parsed when requested.
+    #[cfg(feature = "resource")]
     #[cfg_attr(feature = "serde", serde(skip))]
-    pub resource_data: Option<Vec<u8>>,
+    pub resource_data: ResourceData,
+
+    /// User comment describing the file, which is not written to the EFF file.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub comment: Option<String>,
+
+    /// User tags for the file, which are not written to the EFF file.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tags: Vec<String>,
+
+    /// Names of the authors of the file, which are not written to the EFF file.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub authors: Vec<String>,
 }
 
 impl EffData {
+    /// Constructs new data without effect handles, effect model entries, or resource.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     /// Reads the data from the given file path.
     pub fn from_file<P: AsRef<Path>>(path: P) -> BinResult<Self> {
-        Ok(EffFile::from_file(path)?.into())
+        Self::from_source(&mut FileSystem, path)
     }
 
     /// Reads the data from the given reader.
     pub fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
-        Ok(EffFile::read(reader)?.into())
+        Ok(Self::try_from_eff_file(&EffFile::read(reader)?)?)
+    }
+
+    /// Reads the data from the given reader, which does not need to support seeking.
+    pub fn read_unseekable<R: Read>(reader: &mut R) -> BinResult<Self> {
+        Ok(Self::try_from_eff_file(&EffFile::read_unseekable(reader)?)?)
     }
 
     /// Writes the data to the given writer.
@@ -44,24 +135,1316 @@ impl EffData {
         EffFile::from(self).write(writer)
     }
 
+    /// Writes the data to the given writer, which does not need to support seeking.
+    pub fn write_unseekable<W: Write>(&self, writer: &mut W) -> BinResult<()> {
+        EffFile::from(self).write_unseekable(writer)
+    }
+
     /// Writes the data to the given file path.
     pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
-        EffFile::from(self).write_to_file(path)
+        self.write_to_sink(&mut FileSystem, path)
     }
 
-    /// Writes the data from the resource data buffer to the given file path.
-    pub fn write_resource_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
-        if let Some(resource_data) = &self.resource_data {
+    /// Writes the contained file resource to the given file path, if there is one.
+    #[cfg(feature = "resource")]
+    pub fn write_resource_to_file<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
+        if let Some(resource_data) = self.resource_data.to_bytes()? {
             fs::write(path, resource_data)?;
         }
 
         Ok(())
     }
+
+    /// Returns the data with the given resource, which can be binary data or a parsed [`PtclData`].
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::EffData;
+    ///
+    /// let mut data = EffData::new().with_resource(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(data.set_resource(vec![4, 5]).raw(), Some(&[1, 2, 3][..]));
+    /// assert_eq!(data.take_resource().raw(), Some(&[4, 5][..]));
+    /// assert!(data.resource_data.is_none());
+    /// ```
+    #[cfg(feature = "resource")]
+    pub fn with_resource<R: Into<ResourceData>>(mut self, resource: R) -> Self {
+        self.resource_data = resource.into();
+        self
+    }
+
+    /// Replaces the resource, returning the previous resource.
+    #[cfg(feature = "resource")]
+    pub fn set_resource<R: Into<ResourceData>>(&mut self, resource: R) -> ResourceData {
+        mem::replace(&mut self.resource_data, resource.into())
+    }
+
+    /// Removes the resource, returning it.
+    #[cfg(feature = "resource")]
+    pub fn take_resource(&mut self) -> ResourceData {
+        mem::take(&mut self.resource_data)
+    }
+
+    /// Converts the given [`EffFile`], returning an error instead of panicking if it references missing data or contains invalid names.
+    pub fn try_from_eff_file(value: &EffFile) -> Result<Self, DataError> {
+        Self::convert_eff_file(value, Err)
+    }
+
+    /// Converts the given [`EffFile`], passing each error to `recover` and substituting a placeholder if it returns `Ok`.
+    ///
+    /// Invalid names are decoded lossily, and missing effect models and effect groups are left empty.
+    fn convert_eff_file<F>(value: &EffFile, mut recover: F) -> Result<Self, DataError>
+    where
+        F: FnMut(DataError) -> Result<(), DataError>,
+    {
+        Ok(Self {
+            effect_handles: value
+                .effect_handles
+                .iter()
+                .zip(value.effect_handle_names.iter())
+                .enumerate()
+                .map(|(i, (handle, name))| {
+                    Ok(EffectHandleData {
+                        name: decode_name(name, NameKind::EffectHandle, i, &mut recover)?,
+                        flags: EffectHandleDataFlags {
+                            unk_01: handle.flags.unk_01(),
+                            unk_02: handle.flags.unk_02(),
+                            unk_03: handle.flags.unk_03(),
+                            unk_04: handle.flags.unk_04(),
+                            unk_05: handle.flags.unk_05(),
+                            unk_06: handle.flags.unk_06(),
+                            unk_07: handle.flags.unk_07(),
+                            // unk_08: handle.flags.unk_08(),
+                            unk_09: handle.flags.unk_09(),
+                            unk_10: handle.flags.unk_10(),
+                            // unk_11: handle.flags.unk_11(),
+                            // unk_12: handle.flags.unk_12(),
+                            unk_13: handle.flags.unk_13(),
+                            unk_14: handle.flags.unk_14(),
+                            unk_15: handle.flags.unk_15(),
+                            unk_16: handle.flags.unk_16(),
+                            unk_17: handle.flags.unk_17(),
+                            // unk_18: handle.flags.unk_18(),
+                            hit_effect: handle.flags.hit_effect(),
+                            unk_20: handle.flags.unk_20(),
+                            unk_21: handle.flags.unk_21(),
+                            // unk_22: handle.flags.unk_22(),
+                            unk_23: handle.flags.unk_23(),
+                            update_always: handle.flags.update_always(),
+                            unk_25: handle.flags.unk_25(),
+                            unk_26: handle.flags.unk_26(),
+                            // unk_27: handle.flags.unk_27(),
+                            // unk_28: handle.flags.unk_28(),
+                            unk_29: handle.flags.unk_29(),
+                            unk_30: handle.flags.unk_30(),
+                            unk_31: handle.flags.unk_31(),
+                            unk_32: handle.flags.unk_32(),
+                        },
+                        emitter_set_handle: handle.emitter_set_handle,
+                        effect_model_name: if handle.effect_model_entry_handle != 0 {
+                            let index = handle.effect_model_entry_handle as usize - 1;
+
+                            match value.effect_model_names.get(index) {
+                                Some(name) => {
+                                    decode_name(name, NameKind::EffectModel, index, &mut recover)?
+                                }
+                                None => {
+                                    recover(DataError::EffectModelOutOfRange {
+                                        effect_handle_index: i,
+                                        effect_model_entry_handle: handle.effect_model_entry_handle,
+                                    })?;
+
+                                    String::new()
+                                }
+                            }
+                        } else {
+                            String::new()
+                        },
+                        effect_group: if handle.effect_group_element_count != 0 {
+                            let group = (handle.effect_group_element_start as usize)
+                                .checked_sub(1)
+                                .and_then(|start| {
+                                    let end = start
+                                        .checked_add(handle.effect_group_element_count as usize)?;
+
+                                    Some((
+                                        start,
+                                        value.effect_group_elements.get(start..end)?,
+                                        value.parent_joint_names.get(start..end)?,
+                                    ))
+                                });
+
+                            match group {
+                                Some((start, elements, parent_joint_names)) => elements
+                                    .iter()
+                                    .zip(parent_joint_names.iter())
+                                    .enumerate()
+                                    .map(|(j, (element, parent_joint_name))| {
+                                        Ok(EffectGroupElementData {
+                                            emitter_set_start_frame: element
+                                                .emitter_set_start_frame,
+                                            emitter_set_handle: element.emitter_set_handle,
+                                            parent_joint_name: decode_name(
+                                                parent_joint_name,
+                                                NameKind::ParentJoint,
+                                                start + j,
+                                                &mut recover,
+                                            )?,
+                                            original_index: Some(start + j),
+                                        })
+                                    })
+                                    .collect::<Result<_, DataError>>()?,
+                                None => {
+                                    recover(DataError::EffectGroupOutOfRange {
+                                        effect_handle_index: i,
+                                        effect_group_element_start: handle
+                                            .effect_group_element_start,
+                                        effect_group_element_count: handle
+                                            .effect_group_element_count,
+                                    })?;
+
+                                    Vec::new()
+                                }
+                            }
+                        } else {
+                            Vec::new()
+                        },
+                        comment: None,
+                        tags: Vec::new(),
+                        original_index: Some(i),
+                    })
+                })
+                .collect::<Result<_, DataError>>()?,
+            effect_model_entries: value
+                .effect_model_entries
+                .iter()
+                .zip(value.effect_model_names.iter())
+                .enumerate()
+                .map(|(i, (model, name))| {
+                    Ok(EffectModelEntryData {
+                        name: decode_name(name, NameKind::EffectModel, i, &mut recover)?,
+                        unk: model.unk,
+                        original_index: Some(i),
+                    })
+                })
+                .collect::<Result<_, DataError>>()?,
+            #[cfg(feature = "resource")]
+            resource_data: value.resource_data.clone().into(),
+            comment: None,
+            tags: Vec::new(),
+            authors: Vec::new(),
+        })
+    }
+
+    /// Converts the data to an [`EffFile`], returning an error instead of writing inconsistent tables.
+    ///
+    /// Unlike the [`From`] conversion, effect model names missing from the effect model entries are an error rather than being dropped.
+    pub fn try_to_eff_file(&self) -> Result<EffFile, DataError> {
+        let effect_group_element_count = self
+            .effect_handles
+            .iter()
+            .map(|handle| handle.effect_group.len())
+            .sum::<usize>();
+
+        for (table, count) in [
+            (TableKind::EffectHandles, self.effect_handles.len()),
+            (
+                TableKind::EffectModelEntries,
+                self.effect_model_entries.len(),
+            ),
+            (TableKind::EffectGroupElements, effect_group_element_count),
+        ] {
+            if count > i16::MAX as usize {
+                return Err(DataError::TooManyEntries { table, count });
+            }
+        }
+
+        if let Some(handle) = self.effect_handles.iter().find(|handle| {
+            !handle.effect_model_name.is_empty()
+                && !self
+                    .effect_model_entries
+                    .iter()
+                    .any(|model| model.name == handle.effect_model_name)
+        }) {
+            return Err(DataError::UnknownEffectModel {
+                effect_handle_name: handle.name.clone(),
+                effect_model_name: handle.effect_model_name.clone(),
+            });
+        }
+
+        Ok(self.into())
+    }
+
+    /// Converts the data to an [`EffFile`], writing the effect group elements in the given order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a parsed resource fails to be written, which cannot happen for an in-memory buffer.
+    pub fn to_eff_file(&self, order: GroupElementOrder) -> EffFile {
+        let effect_group_order = self.effect_group_order(order);
+        let mut effect_group_starts = vec![0; self.effect_handles.len()];
+        let mut effect_group_start_index: i16 = 0;
+
+        for &i in &effect_group_order {
+            effect_group_starts[i] = effect_group_start_index + 1;
+            effect_group_start_index += self.effect_handles[i].effect_group.len() as i16;
+        }
+
+        EffFile {
+            effect_handles: self
+                .effect_handles
+                .iter()
+                .zip(effect_group_starts)
+                .map(|(handle, effect_group_element_start)| EffectHandle {
+                    flags: EffectHandleFlags::new()
+                        .with_unk_01(handle.flags.unk_01)
+                        .with_unk_02(handle.flags.unk_02)
+                        .with_unk_03(handle.flags.unk_03)
+                        .with_unk_04(handle.flags.unk_04)
+                        .with_unk_05(handle.flags.unk_05)
+                        .with_unk_06(handle.flags.unk_06)
+                        .with_unk_07(handle.flags.unk_07)
+                        // .with_unk_08(handle.flags.unk_08)
+                        .with_unk_09(handle.flags.unk_09)
+                        .with_unk_10(handle.flags.unk_10)
+                        // .with_unk_11(handle.flags.unk_11)
+                        // .with_unk_12(handle.flags.unk_12)
+                        .with_unk_13(handle.flags.unk_13)
+                        .with_unk_14(handle.flags.unk_14)
+                        .with_unk_15(handle.flags.unk_15)
+                        .with_unk_16(handle.flags.unk_16)
+                        .with_unk_17(handle.flags.unk_17)
+                        // .with_unk_18(handle.flags.unk_18)
+                        .with_hit_effect(handle.flags.hit_effect)
+                        .with_unk_20(handle.flags.unk_20)
+                        .with_unk_21(handle.flags.unk_21)
+                        // .with_unk_22(handle.flags.unk_22)
+                        .with_unk_23(handle.flags.unk_23)
+                        .with_update_always(handle.flags.update_always)
+                        .with_unk_25(handle.flags.unk_25)
+                        .with_unk_26(handle.flags.unk_26)
+                        // .with_unk_27(handle.flags.unk_27)
+                        // .with_unk_28(handle.flags.unk_28)
+                        .with_unk_29(handle.flags.unk_29)
+                        .with_unk_30(handle.flags.unk_30)
+                        .with_unk_31(handle.flags.unk_31)
+                        .with_unk_32(handle.flags.unk_32),
+                    emitter_set_handle: handle.emitter_set_handle,
+                    effect_model_entry_handle: self
+                        .effect_model_entries
+                        .iter()
+                        .position(|model| model.name == handle.effect_model_name)
+                        .map_or(0, |i| i + 1) as i32,
+                    effect_group_element_start,
+                    effect_group_element_count: handle.effect_group.len() as i16,
+                })
+                .collect(),
+            effect_group_elements: effect_group_order
+                .iter()
+                .flat_map(|&i| {
+                    self.effect_handles[i]
+                        .effect_group
+                        .iter()
+                        .map(|element| EffectGroupElement {
+                            emitter_set_start_frame: element.emitter_set_start_frame,
+                            emitter_set_handle: element.emitter_set_handle,
+                        })
+                })
+                .collect(),
+            effect_model_entries: self
+                .effect_model_entries
+                .iter()
+                .map(|model| EffectModelEntry { unk: model.unk })
+                .collect(),
+            effect_handle_names: self
+                .effect_handles
+                .iter()
+                .map(|handle| handle.name.clone().into())
+                .collect(),
+            effect_model_names: self
+                .effect_model_entries
+                .iter()
+                .map(|model| model.name.clone().into())
+                .collect(),
+            parent_joint_names: effect_group_order
+                .iter()
+                .flat_map(|&i| {
+                    self.effect_handles[i]
+                        .effect_group
+                        .iter()
+                        .map(|element| element.parent_joint_name.clone().into())
+                })
+                .collect(),
+            #[cfg(feature = "resource")]
+            resource_data: self
+                .resource_data
+                .to_bytes()
+                .expect("writing a resource to a buffer should not fail")
+                .map(Cow::into_owned),
+            #[cfg(not(feature = "resource"))]
+            resource_data: None,
+        }
+    }
+
+    /// Writes the data to the given writer, writing the effect group elements in the given order.
+    pub fn write_with_order<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        order: GroupElementOrder,
+    ) -> BinResult<()> {
+        self.to_eff_file(order).write(writer)
+    }
+
+    /// Writes the data to the given file path, writing the effect group elements in the given order.
+    pub fn write_to_file_with_order<P: AsRef<Path>>(
+        &self,
+        path: P,
+        order: GroupElementOrder,
+    ) -> BinResult<()> {
+        self.to_eff_file(order).write_to_file(path)
+    }
+
+    /// Returns the indices of the effect handles with effect groups, in the order their elements are written.
+    fn effect_group_order(&self, order: GroupElementOrder) -> Vec<usize> {
+        let mut effect_group_order = (0..self.effect_handles.len())
+            .filter(|&i| !self.effect_handles[i].effect_group.is_empty())
+            .collect::<Vec<_>>();
+
+        if order == GroupElementOrder::Original {
+            effect_group_order.sort_by_key(|&i| {
+                self.effect_handles[i]
+                    .effect_group
+                    .iter()
+                    .filter_map(|element| element.original_index)
+                    .min()
+                    .unwrap_or(usize::MAX)
+            });
+        }
+
+        effect_group_order
+    }
+
+    /// Inserts an effect handle at the given position, shifting all effect handles after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of effect handles.
+    pub fn insert_handle_at(&mut self, index: usize, handle: EffectHandleData) {
+        self.effect_handles.insert(index, handle);
+    }
+
+    /// Inserts an effect handle at the given position whose emitter sets were inserted into the resource.
+    ///
+    /// The resource is expected to contain `emitter_set_count` new emitter sets starting at the positive index `handle.emitter_set_handle`.
+    /// Existing references to emitter sets at or after that index are shifted to keep pointing at the same emitter sets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a shifted reference is out of range, in which case nothing is changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of effect handles.
+    pub fn insert_handle_with_emitter_sets_at(
+        &mut self,
+        index: usize,
+        handle: EffectHandleData,
+        emitter_set_count: i32,
+    ) -> Result<(), DataError> {
+        assert!(
+            index <= self.effect_handles.len(),
+            "effect handle index {index} is out of bounds"
+        );
+
+        self.shift_emitter_set_handles(handle.emitter_set_handle, emitter_set_count)?;
+        self.insert_handle_at(index, handle);
+
+        Ok(())
+    }
+
+    /// Appends an effect handle, adding an effect model entry for its effect model if there is none.
+    ///
+    /// Returns an error if an effect handle with the same name already exists.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{DataError, EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// let handle = EffectHandleData {
+    ///     name: "EFF_SWORD".to_string(),
+    ///     effect_model_name: "sword_trail".to_string(),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// data.add_effect_handle(handle.clone()).unwrap();
+    ///
+    /// assert!(data.find_model_entry("sword_trail").is_some());
+    /// assert_eq!(
+    ///     data.add_effect_handle(handle),
+    ///     Err(DataError::DuplicateEffectHandle {
+    ///         name: "EFF_SWORD".to_string()
+    ///     })
+    /// );
+    /// ```
+    pub fn add_effect_handle(&mut self, handle: EffectHandleData) -> Result<(), DataError> {
+        if self.find_handle(&handle.name).is_some() {
+            return Err(DataError::DuplicateEffectHandle { name: handle.name });
+        }
+
+        if !handle.effect_model_name.is_empty()
+            && self.find_model_entry(&handle.effect_model_name).is_none()
+        {
+            self.effect_model_entries.push(EffectModelEntryData {
+                name: handle.effect_model_name.clone(),
+                unk: EffectModelEntryUnk::default(),
+                original_index: None,
+            });
+        }
+
+        self.effect_handles.push(handle);
+
+        Ok(())
+    }
+
+    /// Appends a copy of the effect handle with the given name, with `suffix` appended to its name.
+    ///
+    /// If `clone_model` is `true` and the effect handle references an effect model, the copy references a copy of the effect model entry with `suffix` appended to its name instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no effect handle with the given name, or if an effect handle with the new name already exists.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// data.add_effect_handle(EffectHandleData {
+    ///     name: "EFF_SWORD".to_string(),
+    ///     effect_model_name: "sword".to_string(),
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// data.clone_handle_with_suffix("EFF_SWORD", "_C01", true).unwrap();
+    ///
+    /// assert_eq!(data.effect_handles[1].name, "EFF_SWORD_C01");
+    /// assert_eq!(data.effect_handles[1].effect_model_name, "sword_C01");
+    /// assert!(data.find_model_entry("sword_C01").is_some());
+    /// ```
+    pub fn clone_handle_with_suffix(
+        &mut self,
+        name: &str,
+        suffix: &str,
+        clone_model: bool,
+    ) -> Result<(), DataError> {
+        let mut handle =
+            self.find_handle(name)
+                .cloned()
+                .ok_or_else(|| DataError::UnknownEffectHandle {
+                    name: name.to_string(),
+                })?;
+
+        handle.name.push_str(suffix);
+        handle.original_index = None;
+
+        for element in &mut handle.effect_group {
+            element.original_index = None;
+        }
+
+        if self.find_handle(&handle.name).is_some() {
+            return Err(DataError::DuplicateEffectHandle { name: handle.name });
+        }
+
+        if clone_model && !handle.effect_model_name.is_empty() {
+            let model = self.find_model_entry(&handle.effect_model_name).cloned();
+
+            handle.effect_model_name.push_str(suffix);
+
+            if let Some(mut model) = model {
+                if self.find_model_entry(&handle.effect_model_name).is_none() {
+                    model.name.clone_from(&handle.effect_model_name);
+                    model.original_index = None;
+                    self.effect_model_entries.push(model);
+                }
+            }
+        }
+
+        self.add_effect_handle(handle)
+    }
+
+    /// Splits the effect handles into separate data by the key returned by `f`, keeping the order of the effect handles within each.
+    ///
+    /// Each part only keeps the effect model entries referenced by its effect handles, and a copy of the annotations and resource of the file.
+    /// Use [`extract_handles`](Self::extract_handles) to also trim the resource.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData, EffectModelEntryData, EffectModelEntryUnk};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for (name, effect_model_name) in [("EFF_FINAL_BEAM", "beam"), ("EFF_SMOKE", "smoke")] {
+    ///     data.add_effect_handle(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         effect_model_name: effect_model_name.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// }
+    ///
+    /// let parts = data.split_by(|handle| handle.name.contains("_FINAL_"));
+    ///
+    /// assert_eq!(parts[&true].effect_handles[0].name, "EFF_FINAL_BEAM");
+    /// assert_eq!(parts[&true].effect_model_entries[0].name, "beam");
+    /// assert_eq!(parts[&false].effect_model_entries[0].name, "smoke");
+    /// ```
+    pub fn split_by<K, F>(&self, mut f: F) -> BTreeMap<K, Self>
+    where
+        K: Ord,
+        F: FnMut(&EffectHandleData) -> K,
+    {
+        let mut parts = BTreeMap::new();
+
+        for handle in &self.effect_handles {
+            parts
+                .entry(f(handle))
+                .or_insert_with(|| Self {
+                    effect_handles: Vec::new(),
+                    effect_model_entries: self.effect_model_entries.clone(),
+                    #[cfg(feature = "resource")]
+                    resource_data: self.resource_data.clone(),
+                    comment: self.comment.clone(),
+                    tags: self.tags.clone(),
+                    authors: self.authors.clone(),
+                })
+                .effect_handles
+                .push(handle.clone());
+        }
+
+        for part in parts.values_mut() {
+            part.remove_unused_model_entries();
+        }
+
+        parts
+    }
+
+    /// Removes the effect handle with the given name, returning it.
+    ///
+    /// If `remove_unused_models` is `true`, effect model entries which are no longer referenced by any remaining effect handle are removed as well.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// data.add_effect_handle(EffectHandleData {
+    ///     name: "EFF_SWORD".to_string(),
+    ///     effect_model_name: "sword_trail".to_string(),
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let handle = data.remove_effect_handle("EFF_SWORD", true).unwrap();
+    ///
+    /// assert_eq!(handle.name, "EFF_SWORD");
+    /// assert!(data.effect_model_entries.is_empty());
+    /// ```
+    pub fn remove_effect_handle(
+        &mut self,
+        name: &str,
+        remove_unused_models: bool,
+    ) -> Result<EffectHandleData, DataError> {
+        let index = self
+            .effect_handles
+            .iter()
+            .position(|handle| handle.name == name)
+            .ok_or_else(|| DataError::UnknownEffectHandle {
+                name: name.to_string(),
+            })?;
+        let handle = self.effect_handles.remove(index);
+
+        if remove_unused_models {
+            self.remove_unused_model_entries();
+        }
+
+        Ok(handle)
+    }
+
+    /// Keeps only the effect handles for which `f` returns `true`, removing the effect model entries referenced only by the removed effect handles.
+    ///
+    /// Effect model entries which were already unreferenced are kept, and can be removed with [`remove_unused_model_entries`](Self::remove_unused_model_entries).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData, EffectModelEntryData, EffectModelEntryUnk};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for (name, effect_model_name) in [("EFF_FINAL_BEAM", "beam"), ("EFF_SMOKE", "smoke")] {
+    ///     data.add_effect_handle(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         effect_model_name: effect_model_name.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// }
+    ///
+    /// data.effect_model_entries.push(EffectModelEntryData {
+    ///     name: "unused".to_string(),
+    ///     unk: EffectModelEntryUnk::default(),
+    ///     original_index: None,
+    /// });
+    ///
+    /// data.retain_handles(|handle| handle.name.contains("_FINAL_"));
+    ///
+    /// assert_eq!(data.effect_handles.len(), 1);
+    /// assert_eq!(data.effect_model_entries.len(), 2);
+    /// assert_eq!(data.effect_model_entries[0].name, "beam");
+    /// assert_eq!(data.effect_model_entries[1].name, "unused");
+    /// ```
+    pub fn retain_handles<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&EffectHandleData) -> bool,
+    {
+        let mut removed_models = HashSet::new();
+
+        self.effect_handles.retain(|handle| {
+            let keep = f(handle);
+
+            if !keep {
+                removed_models.insert(handle.effect_model_name.clone());
+            }
+
+            keep
+        });
+
+        let effect_handles = &self.effect_handles;
+
+        self.effect_model_entries.retain(|model| {
+            !removed_models.contains(&model.name)
+                || effect_handles
+                    .iter()
+                    .any(|handle| handle.effect_model_name == model.name)
+        });
+    }
+
+    /// Sorts the effect handles by name.
+    ///
+    /// Effect group elements are packed in the new effect handle order when written with [`GroupElementOrder::Packed`].
+    pub fn sort_handles_by_name(&mut self) {
+        self.sort_handles_by(|a, b| a.name.cmp(&b.name));
+    }
+
+    /// Sorts the effect handles with the given comparator, keeping the order of equal effect handles.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for (name, emitter_set_handle) in [("EFF_B", 1), ("EFF_A", 2), ("EFF_C", 1)] {
+    ///     data.add_effect_handle(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         emitter_set_handle,
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// }
+    ///
+    /// data.sort_handles_by(|a, b| a.emitter_set_handle.cmp(&b.emitter_set_handle));
+    ///
+    /// let names: Vec<_> = data.effect_handles.iter().map(|handle| handle.name.as_str()).collect();
+    ///
+    /// assert_eq!(names, ["EFF_B", "EFF_C", "EFF_A"]);
+    /// ```
+    pub fn sort_handles_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&EffectHandleData, &EffectHandleData) -> Ordering,
+    {
+        self.effect_handles.sort_by(compare);
+    }
+
+    /// Returns the effect handles which reference the effect model with the given name.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for (name, effect_model_name) in [("EFF_SLASH", "sword"), ("EFF_GUARD", "shield")] {
+    ///     data.add_effect_handle(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         effect_model_name: effect_model_name.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// }
+    ///
+    /// let names: Vec<_> = data.handles_for_model("sword").map(|handle| handle.name.as_str()).collect();
+    ///
+    /// assert_eq!(names, ["EFF_SLASH"]);
+    /// ```
+    pub fn handles_for_model<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a EffectHandleData> + 'a {
+        self.effect_handles
+            .iter()
+            .filter(move |handle| handle.effect_model_name == name)
+    }
+
+    /// Returns the effect group elements which are parented to the joint with the given name, along with their effect handles.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectGroupElementData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// data.add_effect_handle(EffectHandleData {
+    ///     name: "EFF_TRAIL".to_string(),
+    ///     effect_group: vec![
+    ///         EffectGroupElementData {
+    ///             parent_joint_name: "haver".to_string(),
+    ///             ..Default::default()
+    ///         },
+    ///         EffectGroupElementData {
+    ///             parent_joint_name: "hip".to_string(),
+    ///             ..Default::default()
+    ///         },
+    ///     ],
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let (handle, element) = data.handles_using_joint("haver").next().unwrap();
+    ///
+    /// assert_eq!(handle.name, "EFF_TRAIL");
+    /// assert_eq!(element.parent_joint_name, "haver");
+    /// assert_eq!(data.handles_using_joint("haver").count(), 1);
+    /// ```
+    pub fn handles_using_joint<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = (&'a EffectHandleData, &'a EffectGroupElementData)> + 'a {
+        self.effect_handles.iter().flat_map(move |handle| {
+            handle
+                .effect_group
+                .iter()
+                .filter(move |element| element.parent_joint_name == name)
+                .map(move |element| (handle, element))
+        })
+    }
+
+    /// Renames the parent joint of every effect group element whose joint is a key of `joints` to the corresponding value, returning the number of elements changed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use eff_data::{EffData, EffectGroupElementData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// data.add_effect_handle(EffectHandleData {
+    ///     name: "EFF_TRAIL".to_string(),
+    ///     effect_group: vec![EffectGroupElementData {
+    ///         parent_joint_name: "haver".to_string(),
+    ///         ..Default::default()
+    ///     }],
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let joints = HashMap::from([("haver".to_string(), "sword1".to_string())]);
+    ///
+    /// assert_eq!(data.remap_parent_joints(&joints), 1);
+    /// assert_eq!(data.effect_handles[0].effect_group[0].parent_joint_name, "sword1");
+    /// ```
+    pub fn remap_parent_joints(&mut self, joints: &HashMap<String, String>) -> usize {
+        let mut count = 0;
+
+        for handle in &mut self.effect_handles {
+            for element in &mut handle.effect_group {
+                if let Some(joint) = joints.get(&element.parent_joint_name) {
+                    element.parent_joint_name.clone_from(joint);
+                    count += 1;
+                }
+            }
+        }
+
+        count
+    }
+
+    /// Shifts the start frames of the effect group elements of every effect handle for which `f` returns `true`.
+    ///
+    /// See [`EffectHandleData::shift_start_frames`] for how start frames are clamped.
+    pub fn shift_start_frames<F>(&mut self, delta: i16, mut f: F)
+    where
+        F: FnMut(&EffectHandleData) -> bool,
+    {
+        for handle in &mut self.effect_handles {
+            if f(handle) {
+                handle.shift_start_frames(delta);
+            }
+        }
+    }
+
+    /// Normalizes the data into a stable form for diffing and hashing.
+    ///
+    /// Names are trimmed of surrounding whitespace, effect model entries without a name are removed, duplicate effect model entries are removed with [`deduplicate_model_entries`](Self::deduplicate_model_entries), and effect group elements are ordered by start frame.
+    ///
+    /// Effect handles without an effect model have an empty effect model name, so an effect model entry without a name cannot be referenced and is removed.
+    /// This includes entries whose name is only whitespace, and effect handles referencing them are left without an effect model.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if two effect handles have the same name after trimming, in which case the data is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{DataError, EffData, EffectHandleData, EffectModelEntryData};
+    ///
+    /// let mut data = EffData::new();
+    /// data.effect_handles.push(EffectHandleData {
+    ///     name: " EFF_SWORD".to_string(),
+    ///     effect_model_name: "sword ".to_string(),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// for name in ["sword", "sword ", " "] {
+    ///     data.effect_model_entries.push(EffectModelEntryData {
+    ///         name: name.to_string(),
+    ///         unk: Default::default(),
+    ///         original_index: None,
+    ///     });
+    /// }
+    ///
+    /// data.canonicalize().unwrap();
+    ///
+    /// assert_eq!(data.effect_handles[0].name, "EFF_SWORD");
+    /// assert_eq!(data.effect_handles[0].effect_model_name, "sword");
+    /// assert_eq!(data.effect_model_entries.len(), 1);
+    ///
+    /// data.effect_handles.push(EffectHandleData {
+    ///     name: "EFF_SWORD ".to_string(),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// assert_eq!(
+    ///     data.canonicalize(),
+    ///     Err(DataError::DuplicateEffectHandle {
+    ///         name: "EFF_SWORD".to_string()
+    ///     })
+    /// );
+    /// assert_eq!(data.effect_handles[1].name, "EFF_SWORD ");
+    /// ```
+    pub fn canonicalize(&mut self) -> Result<(), DataError> {
+        let mut names = HashSet::new();
+
+        for handle in &self.effect_handles {
+            let name = handle.name.trim();
+
+            if !names.insert(name) {
+                return Err(DataError::DuplicateEffectHandle {
+                    name: name.to_string(),
+                });
+            }
+        }
+
+        for handle in &mut self.effect_handles {
+            trim_name(&mut handle.name);
+            trim_name(&mut handle.effect_model_name);
+
+            for element in &mut handle.effect_group {
+                trim_name(&mut element.parent_joint_name);
+            }
+
+            handle.sort_effect_group();
+        }
+
+        for model in &mut self.effect_model_entries {
+            trim_name(&mut model.name);
+        }
+
+        self.effect_model_entries
+            .retain(|model| !model.name.is_empty());
+        self.deduplicate_model_entries();
+
+        Ok(())
+    }
+
+    /// Removes every effect model entry whose name matches an earlier entry, returning the number of entries removed.
+    ///
+    /// Effect handles reference effect models by name, so they keep pointing at the first entry with each name.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectModelEntryData};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for name in ["sword", "shield", "sword"] {
+    ///     data.effect_model_entries.push(EffectModelEntryData {
+    ///         name: name.to_string(),
+    ///         unk: Default::default(),
+    ///         original_index: None,
+    ///     });
+    /// }
+    ///
+    /// assert_eq!(data.deduplicate_model_entries(), 1);
+    /// assert_eq!(data.effect_model_entries.len(), 2);
+    /// ```
+    pub fn deduplicate_model_entries(&mut self) -> usize {
+        let count = self.effect_model_entries.len();
+        let mut names = HashSet::new();
+
+        self.effect_model_entries
+            .retain(|model| names.insert(model.name.clone()));
+
+        count - self.effect_model_entries.len()
+    }
+
+    /// Removes every effect model entry which is not referenced by an effect handle, returning the number of entries removed.
+    pub fn remove_unused_model_entries(&mut self) -> usize {
+        let count = self.effect_model_entries.len();
+        let effect_handles = &self.effect_handles;
+
+        self.effect_model_entries.retain(|model| {
+            effect_handles
+                .iter()
+                .any(|handle| handle.effect_model_name == model.name)
+        });
+
+        count - self.effect_model_entries.len()
+    }
+
+    /// Renames the effect handle named `old` to `new`.
+    ///
+    /// Returns an error if there is no effect handle named `old`, or if another effect handle is already named `new`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData, RenameError};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for name in ["EFF_SMOKE", "EFF_SPARK"] {
+    ///     data.add_effect_handle(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// }
+    ///
+    /// data.rename_effect_handle("EFF_SMOKE", "EFF_DUST").unwrap();
+    ///
+    /// assert!(data.find_handle("EFF_DUST").is_some());
+    /// assert_eq!(
+    ///     data.rename_effect_handle("EFF_DUST", "EFF_SPARK"),
+    ///     Err(RenameError::Duplicate {
+    ///         name: "EFF_SPARK".to_string()
+    ///     })
+    /// );
+    /// ```
+    pub fn rename_effect_handle(&mut self, old: &str, new: &str) -> Result<(), RenameError> {
+        if self.find_handle(old).is_none() {
+            return Err(RenameError::Missing {
+                name: old.to_string(),
+            });
+        }
+
+        if old != new && self.find_handle(new).is_some() {
+            return Err(RenameError::Duplicate {
+                name: new.to_string(),
+            });
+        }
+
+        if let Some(handle) = self.find_handle_mut(old) {
+            handle.name = new.to_string();
+        }
+
+        Ok(())
+    }
+
+    /// Replaces every positive index to an emitter set with the index it maps to in `handles`.
+    ///
+    /// This keeps references consistent after emitter sets are reordered, inserted into, or removed from the resource, or merged into another resource with [`PtclData::merge`].
+    /// Every referenced emitter set must be mapped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a positive index has no mapping, or if a new index does not fit in an effect group element, in which case nothing is changed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// let mut handle = EffectHandleData {
+    ///     name: "EFF_SPARK".to_string(),
+    ///     emitter_set_handle: 1,
+    ///     ..Default::default()
+    /// };
+    /// handle.add_group_element(0, 2, "hip".to_string());
+    /// data.add_effect_handle(handle).unwrap();
+    ///
+    /// assert!(data.remap_emitter_set_handles(&HashMap::from([(1, 2)])).is_err());
+    /// assert!(data
+    ///     .remap_emitter_set_handles(&HashMap::from([(1, 2), (2, 0x8000)]))
+    ///     .is_err());
+    ///
+    /// data.remap_emitter_set_handles(&HashMap::from([(1, 2), (2, 1)])).unwrap();
+    ///
+    /// assert_eq!(data.effect_handles[0].emitter_set_handle, 2);
+    /// assert_eq!(data.effect_handles[0].effect_group[0].emitter_set_handle, 1);
+    /// ```
+    pub fn remap_emitter_set_handles(
+        &mut self,
+        handles: &HashMap<i32, i32>,
+    ) -> Result<(), DataError> {
+        let mut effect_handles = self.effect_handles.clone();
+
+        for handle in &mut effect_handles {
+            let remap = |emitter_set_handle: i32| {
+                if emitter_set_handle <= 0 {
+                    return Ok(emitter_set_handle);
+                }
+
+                handles.get(&emitter_set_handle).copied().ok_or_else(|| {
+                    DataError::UnmappedEmitterSet {
+                        emitter_set_handle,
+                        effect_handle_name: handle.name.clone(),
+                    }
+                })
+            };
+
+            let emitter_set_handle = remap(handle.emitter_set_handle)?;
+            let mut effect_group = handle.effect_group.clone();
+
+            for element in &mut effect_group {
+                let emitter_set_handle = remap(element.emitter_set_handle as i32)?;
+
+                element.emitter_set_handle = i16::try_from(emitter_set_handle).map_err(|_| {
+                    DataError::EmitterSetHandleOutOfRange {
+                        emitter_set_handle: emitter_set_handle.into(),
+                        effect_handle_name: handle.name.clone(),
+                    }
+                })?;
+            }
+
+            handle.emitter_set_handle = emitter_set_handle;
+            handle.effect_group = effect_group;
+        }
+
+        self.effect_handles = effect_handles;
+
+        Ok(())
+    }
+
+    /// Adds `delta` to every positive index to an emitter set which is greater than or equal to `start`.
+    ///
+    /// This keeps references consistent after emitter sets are inserted into or removed from the resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a shifted index is no longer positive or does not fit in the effect handle or effect group element referencing it, in which case nothing is changed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// let mut handle = EffectHandleData {
+    ///     name: "EFF_SPARK".to_string(),
+    ///     emitter_set_handle: 1,
+    ///     ..Default::default()
+    /// };
+    /// handle.add_group_element(0, 3, "hip".to_string());
+    /// data.add_effect_handle(handle).unwrap();
+    ///
+    /// data.shift_emitter_set_handles(2, 1).unwrap();
+    ///
+    /// assert_eq!(data.effect_handles[0].emitter_set_handle, 1);
+    /// assert_eq!(data.effect_handles[0].effect_group[0].emitter_set_handle, 4);
+    /// assert!(data.shift_emitter_set_handles(2, i16::MAX as i32).is_err());
+    /// assert_eq!(data.effect_handles[0].effect_group[0].emitter_set_handle, 4);
+    /// ```
+    pub fn shift_emitter_set_handles(&mut self, start: i32, delta: i32) -> Result<(), DataError> {
+        let start = start.max(1);
+        let mut effect_handles = self.effect_handles.clone();
+
+        for handle in &mut effect_handles {
+            let shift = |emitter_set_handle: i32| {
+                if emitter_set_handle < start {
+                    return Ok(emitter_set_handle);
+                }
+
+                emitter_set_handle
+                    .checked_add(delta)
+                    .filter(|emitter_set_handle| *emitter_set_handle > 0)
+                    .ok_or_else(|| DataError::EmitterSetHandleOutOfRange {
+                        emitter_set_handle: emitter_set_handle as i64 + delta as i64,
+                        effect_handle_name: handle.name.clone(),
+                    })
+            };
+
+            let emitter_set_handle = shift(handle.emitter_set_handle)?;
+            let mut effect_group = handle.effect_group.clone();
+
+            for element in &mut effect_group {
+                let emitter_set_handle = shift(element.emitter_set_handle as i32)?;
+
+                element.emitter_set_handle = i16::try_from(emitter_set_handle).map_err(|_| {
+                    DataError::EmitterSetHandleOutOfRange {
+                        emitter_set_handle: emitter_set_handle.into(),
+                        effect_handle_name: handle.name.clone(),
+                    }
+                })?;
+            }
+
+            handle.emitter_set_handle = emitter_set_handle;
+            handle.effect_group = effect_group;
+        }
+
+        self.effect_handles = effect_handles;
+
+        Ok(())
+    }
+
+    /// Returns the first effect handle with the given name.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// data.effect_handles.push(EffectHandleData {
+    ///     name: "EFF_SPARK".to_string(),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// data.find_handle_mut("EFF_SPARK").unwrap().emitter_set_handle = 2;
+    ///
+    /// assert_eq!(data.find_handle("EFF_SPARK").unwrap().emitter_set_handle, 2);
+    /// assert!(data.find_handle("EFF_SMOKE").is_none());
+    /// ```
+    pub fn find_handle(&self, name: &str) -> Option<&EffectHandleData> {
+        self.effect_handles
+            .iter()
+            .find(|handle| handle.name == name)
+    }
+
+    /// Returns a mutable reference to the first effect handle with the given name.
+    pub fn find_handle_mut(&mut self, name: &str) -> Option<&mut EffectHandleData> {
+        self.effect_handles
+            .iter_mut()
+            .find(|handle| handle.name == name)
+    }
+
+    /// Returns the first effect model entry with the given name.
+    pub fn find_model_entry(&self, name: &str) -> Option<&EffectModelEntryData> {
+        self.effect_model_entries
+            .iter()
+            .find(|model| model.name == name)
+    }
+
+    /// Returns a mutable reference to the first effect model entry with the given name.
+    pub fn find_model_entry_mut(&mut self, name: &str) -> Option<&mut EffectModelEntryData> {
+        self.effect_model_entries
+            .iter_mut()
+            .find(|model| model.name == name)
+    }
+}
+
+fn trim_name(name: &mut String) {
+    let trimmed = name.trim();
+
+    if trimmed.len() != name.len() {
+        *name = trimmed.to_string();
+    }
+}
+
+fn decode_name<F>(
+    name: &CString,
+    table: NameKind,
+    index: usize,
+    recover: &mut F,
+) -> Result<String, DataError>
+where
+    F: FnMut(DataError) -> Result<(), DataError>,
+{
+    name.to_string().or_else(|error| {
+        recover(DataError::InvalidName {
+            table,
+            index,
+            error,
+        })?;
+
+        Ok(name.to_string_lossy())
+    })
+}
+
+/// Converts a positive index to an emitter set into a zero-based index, if it refers to one.
+pub(crate) fn emitter_set_index(handle: i32) -> Option<usize> {
+    (handle > 0).then(|| handle as usize - 1)
 }
 
 /// The data associated with an [`EffectHandle`].
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default)]
 pub struct EffectHandleData {
     /// Name of the effect handle.
     pub name: String,
@@ -77,11 +1460,145 @@ pub struct EffectHandleData {
 
     /// Collection of effect group elements.
     pub effect_group: Vec<EffectGroupElementData>,
+
+    /// User comment describing the effect handle, which is not written to the EFF file.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub comment: Option<String>,
+
+    /// User tags for the effect handle, which are not written to the EFF file.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tags: Vec<String>,
+
+    /// Index of the effect handle in the table it was read from, if any.
+    ///
+    /// This is not compared for equality.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_index: Option<usize>,
+}
+
+impl PartialEq for EffectHandleData {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.flags == other.flags
+            && self.emitter_set_handle == other.emitter_set_handle
+            && self.effect_model_name == other.effect_model_name
+            && self.effect_group == other.effect_group
+            && self.comment == other.comment
+            && self.tags == other.tags
+    }
+}
+
+impl EffectHandleData {
+    /// Constructs a new effect handle without a name, effect group elements, or flags set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts an effect group element after every element starting on the same or an earlier frame, returning its index.
+    ///
+    /// The effect group elements are expected to already be ordered by start frame.
+    pub fn insert_group_element_sorted(&mut self, element: EffectGroupElementData) -> usize {
+        let index = self.effect_group.partition_point(|other| {
+            other.emitter_set_start_frame <= element.emitter_set_start_frame
+        });
+
+        self.effect_group.insert(index, element);
+
+        index
+    }
+
+    /// Adds an effect group element requesting the emitter set with the given positive index on the given frame, returning its index.
+    ///
+    /// The element is inserted with [`insert_group_element_sorted`](Self::insert_group_element_sorted), and is packed with the rest of the effect group when written.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::EffectHandleData;
+    ///
+    /// let mut handle = EffectHandleData::default();
+    ///
+    /// assert_eq!(handle.add_group_element(10, 1, "hip".to_string()), 0);
+    /// assert_eq!(handle.add_group_element(0, 2, "head".to_string()), 0);
+    ///
+    /// let element = handle.remove_group_element(1);
+    ///
+    /// assert_eq!(element.parent_joint_name, "hip");
+    /// assert_eq!(handle.effect_group.len(), 1);
+    /// ```
+    pub fn add_group_element(
+        &mut self,
+        start_frame: i16,
+        emitter_set_handle: i16,
+        parent_joint_name: String,
+    ) -> usize {
+        self.insert_group_element_sorted(EffectGroupElementData {
+            emitter_set_start_frame: start_frame,
+            emitter_set_handle,
+            parent_joint_name,
+            original_index: None,
+        })
+    }
+
+    /// Removes the effect group element at the given position, returning it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_group_element(&mut self, index: usize) -> EffectGroupElementData {
+        self.effect_group.remove(index)
+    }
+
+    /// Adds `delta` to the start frame of every effect group element, clamping start frames to between `0` and `i16::MAX`.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::EffectHandleData;
+    ///
+    /// let mut handle = EffectHandleData::default();
+    /// handle.add_group_element(2, 1, "hip".to_string());
+    /// handle.add_group_element(10, 1, "hip".to_string());
+    ///
+    /// handle.shift_start_frames(-5);
+    ///
+    /// assert_eq!(handle.effect_group[0].emitter_set_start_frame, 0);
+    /// assert_eq!(handle.effect_group[1].emitter_set_start_frame, 5);
+    /// ```
+    pub fn shift_start_frames(&mut self, delta: i16) {
+        for element in &mut self.effect_group {
+            element.emitter_set_start_frame =
+                element.emitter_set_start_frame.saturating_add(delta).max(0);
+        }
+    }
+
+    /// Returns `true` if the effect group elements are ordered by start frame, and `false` otherwise.
+    pub fn is_effect_group_sorted(&self) -> bool {
+        self.effect_group
+            .windows(2)
+            .all(|pair| pair[0].emitter_set_start_frame <= pair[1].emitter_set_start_frame)
+    }
+
+    /// Orders the effect group elements by start frame, preserving the order of elements starting on the same frame.
+    pub fn sort_effect_group(&mut self) {
+        self.effect_group
+            .sort_by_key(|element| element.emitter_set_start_frame);
+    }
 }
 
 /// Flags for an [`EffectHandleData`] representing the attributes of an effect.
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
 pub struct EffectHandleDataFlags {
     pub unk_01: bool,
     pub unk_02: bool,
@@ -117,9 +1634,228 @@ pub struct EffectHandleDataFlags {
     pub unk_32: bool,
 }
 
+impl EffectHandleDataFlags {
+    /// Constructs new flags with no flag set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bit index and field name of every known flag, in field order.
+    pub(crate) const FIELDS: [(u32, &'static str); 25] = [
+        (0, "unk_01"),
+        (1, "unk_02"),
+        (2, "unk_03"),
+        (3, "unk_04"),
+        (4, "unk_05"),
+        (5, "unk_06"),
+        (6, "unk_07"),
+        (8, "unk_09"),
+        (9, "unk_10"),
+        (12, "unk_13"),
+        (13, "unk_14"),
+        (14, "unk_15"),
+        (15, "unk_16"),
+        (16, "unk_17"),
+        (18, "hit_effect"),
+        (19, "unk_20"),
+        (20, "unk_21"),
+        (22, "unk_23"),
+        (23, "update_always"),
+        (24, "unk_25"),
+        (25, "unk_26"),
+        (28, "unk_29"),
+        (29, "unk_30"),
+        (30, "unk_31"),
+        (31, "unk_32"),
+    ];
+
+    /// Constructs flags from the bits of an [`EffectHandleFlags`], ignoring the bits which are not stored.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::EffectHandleDataFlags;
+    ///
+    /// let mut flags = EffectHandleDataFlags::from_bits(1 << 18);
+    ///
+    /// assert!(flags.hit_effect);
+    /// assert!(flags.set_bit(23, true));
+    /// assert_eq!(flags.get_bit(23), Some(true));
+    /// assert_eq!(flags.get_bit(7), None);
+    /// assert_eq!(flags.to_bits(), 1 << 18 | 1 << 23);
+    /// ```
+    pub fn from_bits(bits: u32) -> Self {
+        let mut flags = Self::default();
+
+        for ((bit, _), value) in Self::FIELDS.iter().zip(flags.values_mut()) {
+            *value = bits & (1 << bit) != 0;
+        }
+
+        flags
+    }
+
+    /// Returns the bits of the flags as stored in an [`EffectHandleFlags`].
+    pub fn to_bits(&self) -> u32 {
+        Self::FIELDS
+            .iter()
+            .zip(self.values())
+            .filter(|(_, value)| *value)
+            .fold(0, |bits, ((bit, _), _)| bits | 1 << bit)
+    }
+
+    /// Returns the value of the flag at the given bit index, or `None` if the bit is not stored.
+    pub fn get_bit(&self, index: u32) -> Option<bool> {
+        Self::FIELDS
+            .iter()
+            .zip(self.values())
+            .find(|((bit, _), _)| *bit == index)
+            .map(|(_, value)| value)
+    }
+
+    /// Sets the value of the flag at the given bit index, returning `false` without changing anything if the bit is not stored.
+    pub fn set_bit(&mut self, index: u32, value: bool) -> bool {
+        match Self::FIELDS
+            .iter()
+            .zip(self.values_mut())
+            .find(|((bit, _), _)| *bit == index)
+        {
+            Some((_, flag)) => {
+                *flag = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the field names of the flags which are set, in field order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::EffectHandleDataFlags;
+    ///
+    /// let flags = EffectHandleDataFlags {
+    ///     hit_effect: true,
+    ///     update_always: true,
+    ///     ..Default::default()
+    /// };
+    ///
+    /// assert!(flags.set_flags().eq(["hit_effect", "update_always"]));
+    /// ```
+    pub fn set_flags(&self) -> impl Iterator<Item = &'static str> {
+        Self::FIELDS
+            .iter()
+            .zip(self.values())
+            .filter(|(_, value)| *value)
+            .map(|((_, name), _)| *name)
+    }
+
+    /// Returns the value of every known flag, in field order.
+    pub(crate) fn values(&self) -> [bool; 25] {
+        [
+            self.unk_01,
+            self.unk_02,
+            self.unk_03,
+            self.unk_04,
+            self.unk_05,
+            self.unk_06,
+            self.unk_07,
+            self.unk_09,
+            self.unk_10,
+            self.unk_13,
+            self.unk_14,
+            self.unk_15,
+            self.unk_16,
+            self.unk_17,
+            self.hit_effect,
+            self.unk_20,
+            self.unk_21,
+            self.unk_23,
+            self.update_always,
+            self.unk_25,
+            self.unk_26,
+            self.unk_29,
+            self.unk_30,
+            self.unk_31,
+            self.unk_32,
+        ]
+    }
+
+    /// Returns a mutable reference to every known flag, in field order.
+    fn values_mut(&mut self) -> [&mut bool; 25] {
+        [
+            &mut self.unk_01,
+            &mut self.unk_02,
+            &mut self.unk_03,
+            &mut self.unk_04,
+            &mut self.unk_05,
+            &mut self.unk_06,
+            &mut self.unk_07,
+            &mut self.unk_09,
+            &mut self.unk_10,
+            &mut self.unk_13,
+            &mut self.unk_14,
+            &mut self.unk_15,
+            &mut self.unk_16,
+            &mut self.unk_17,
+            &mut self.hit_effect,
+            &mut self.unk_20,
+            &mut self.unk_21,
+            &mut self.unk_23,
+            &mut self.update_always,
+            &mut self.unk_25,
+            &mut self.unk_26,
+            &mut self.unk_29,
+            &mut self.unk_30,
+            &mut self.unk_31,
+            &mut self.unk_32,
+        ]
+    }
+}
+
+impl fmt::Display for EffectHandleDataFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_flag_names(f, self.set_flags())
+    }
+}
+
+/// Writes the given flag names as a comma-separated list, or `none` if there are none.
+pub(crate) fn write_flag_names<'a, I>(f: &mut fmt::Formatter<'_>, mut names: I) -> fmt::Result
+where
+    I: Iterator<Item = &'a str>,
+{
+    match names.next() {
+        Some(name) => write!(f, "{name}")?,
+        None => return write!(f, "none"),
+    }
+
+    for name in names {
+        write!(f, ", {name}")?;
+    }
+
+    Ok(())
+}
+
+/// The order to write the effect group elements of an [`EffData`] in.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum GroupElementOrder {
+    /// Pack the effect group elements in effect handle order.
+    #[default]
+    Packed,
+
+    /// Keep the effect group elements in the order they were read in, as recorded by [`EffectGroupElementData::original_index`].
+    ///
+    /// Each effect group stays contiguous, and effect groups without recorded indices are written last in effect handle order.
+    Original,
+}
+
 /// The data associated with an [`EffectGroupElement`].
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default)]
 pub struct EffectGroupElementData {
     /// Frame to request the emitter set on.
     pub emitter_set_start_frame: i16,
@@ -129,17 +1865,50 @@ pub struct EffectGroupElementData {
 
     /// Joint name to parent the emitter set to.
     pub parent_joint_name: String,
+
+    /// Index of the element in the table it was read from, if any.
+    ///
+    /// This is not compared for equality.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_index: Option<usize>,
+}
+
+impl EffectGroupElementData {
+    /// Constructs a new effect group element which starts on the first frame without an emitter set or parent joint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PartialEq for EffectGroupElementData {
+    fn eq(&self, other: &Self) -> bool {
+        self.emitter_set_start_frame == other.emitter_set_start_frame
+            && self.emitter_set_handle == other.emitter_set_handle
+            && self.parent_joint_name == other.parent_joint_name
+    }
 }
 
 /// The data associated with an [`EffectModelEntry`].
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct EffectModelEntryData {
     /// Name of the effect model.
     pub name: String,
 
     // TODO: Determine the purpose of this field.
-    pub unk: i8,
+    pub unk: EffectModelEntryUnk,
+
+    /// Index of the effect model entry in the table it was read from, if any.
+    ///
+    /// This is not compared for equality.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub original_index: Option<usize>,
+}
+
+impl PartialEq for EffectModelEntryData {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.unk == other.unk
+    }
 }
 
 impl From<EffFile> for EffData {
@@ -150,84 +1919,7 @@ impl From<EffFile> for EffData {
 
 impl From<&EffFile> for EffData {
     fn from(value: &EffFile) -> Self {
-        Self {
-            effect_handles: value
-                .effect_handles
-                .iter()
-                .zip(value.effect_handle_names.iter())
-                .map(|(handle, name)| EffectHandleData {
-                    name: name.to_string().unwrap(),
-                    flags: EffectHandleDataFlags {
-                        unk_01: handle.flags.unk_01(),
-                        unk_02: handle.flags.unk_02(),
-                        unk_03: handle.flags.unk_03(),
-                        unk_04: handle.flags.unk_04(),
-                        unk_05: handle.flags.unk_05(),
-                        unk_06: handle.flags.unk_06(),
-                        unk_07: handle.flags.unk_07(),
-                        // unk_08: handle.flags.unk_08(),
-                        unk_09: handle.flags.unk_09(),
-                        unk_10: handle.flags.unk_10(),
-                        // unk_11: handle.flags.unk_11(),
-                        // unk_12: handle.flags.unk_12(),
-                        unk_13: handle.flags.unk_13(),
-                        unk_14: handle.flags.unk_14(),
-                        unk_15: handle.flags.unk_15(),
-                        unk_16: handle.flags.unk_16(),
-                        unk_17: handle.flags.unk_17(),
-                        // unk_18: handle.flags.unk_18(),
-                        hit_effect: handle.flags.hit_effect(),
-                        unk_20: handle.flags.unk_20(),
-                        unk_21: handle.flags.unk_21(),
-                        // unk_22: handle.flags.unk_22(),
-                        unk_23: handle.flags.unk_23(),
-                        update_always: handle.flags.update_always(),
-                        unk_25: handle.flags.unk_25(),
-                        unk_26: handle.flags.unk_26(),
-                        // unk_27: handle.flags.unk_27(),
-                        // unk_28: handle.flags.unk_28(),
-                        unk_29: handle.flags.unk_29(),
-                        unk_30: handle.flags.unk_30(),
-                        unk_31: handle.flags.unk_31(),
-                        unk_32: handle.flags.unk_32(),
-                    },
-                    emitter_set_handle: handle.emitter_set_handle,
-                    effect_model_name: if handle.effect_model_entry_handle != 0 {
-                        value.effect_model_names[handle.effect_model_entry_handle as usize - 1]
-                            .to_string()
-                            .unwrap()
-                    } else {
-                        String::new()
-                    },
-                    effect_group: if handle.effect_group_element_count != 0 {
-                        let start = handle.effect_group_element_start as usize - 1;
-                        let end = start + handle.effect_group_element_count as usize;
-
-                        value.effect_group_elements[start..end]
-                            .iter()
-                            .zip(value.parent_joint_names[start..end].iter())
-                            .map(|(element, parent_joint_name)| EffectGroupElementData {
-                                emitter_set_start_frame: element.emitter_set_start_frame,
-                                emitter_set_handle: element.emitter_set_handle,
-                                parent_joint_name: parent_joint_name.to_string().unwrap(),
-                            })
-                            .collect()
-                    } else {
-                        Vec::new()
-                    },
-                })
-                .collect(),
-            effect_model_entries: value
-                .effect_model_entries
-                .iter()
-                .zip(value.effect_model_names.iter())
-                .map(|(model, name)| EffectModelEntryData {
-                    name: name.to_string().unwrap(),
-                    unk: model.unk,
-                })
-                .collect(),
-            resource_data: value.resource_data.clone(),
-        }
+        Self::try_from_eff_file(value).unwrap_or_else(|error| panic!("{error}"))
     }
 }
 
@@ -239,103 +1931,6 @@ impl From<EffData> for EffFile {
 
 impl From<&EffData> for EffFile {
     fn from(value: &EffData) -> Self {
-        let mut effect_group_start_index: i16 = 0;
-
-        Self {
-            effect_handles: value
-                .effect_handles
-                .iter()
-                .map(|handle| EffectHandle {
-                    flags: EffectHandleFlags::new()
-                        .with_unk_01(handle.flags.unk_01)
-                        .with_unk_02(handle.flags.unk_02)
-                        .with_unk_03(handle.flags.unk_03)
-                        .with_unk_04(handle.flags.unk_04)
-                        .with_unk_05(handle.flags.unk_05)
-                        .with_unk_06(handle.flags.unk_06)
-                        .with_unk_07(handle.flags.unk_07)
-                        // .with_unk_08(handle.flags.unk_08)
-                        .with_unk_09(handle.flags.unk_09)
-                        .with_unk_10(handle.flags.unk_10)
-                        // .with_unk_11(handle.flags.unk_11)
-                        // .with_unk_12(handle.flags.unk_12)
-                        .with_unk_13(handle.flags.unk_13)
-                        .with_unk_14(handle.flags.unk_14)
-                        .with_unk_15(handle.flags.unk_15)
-                        .with_unk_16(handle.flags.unk_16)
-                        .with_unk_17(handle.flags.unk_17)
-                        // .with_unk_18(handle.flags.unk_18)
-                        .with_hit_effect(handle.flags.hit_effect)
-                        .with_unk_20(handle.flags.unk_20)
-                        .with_unk_21(handle.flags.unk_21)
-                        // .with_unk_22(handle.flags.unk_22)
-                        .with_unk_23(handle.flags.unk_23)
-                        .with_update_always(handle.flags.update_always)
-                        .with_unk_25(handle.flags.unk_25)
-                        .with_unk_26(handle.flags.unk_26)
-                        // .with_unk_27(handle.flags.unk_27)
-                        // .with_unk_28(handle.flags.unk_28)
-                        .with_unk_29(handle.flags.unk_29)
-                        .with_unk_30(handle.flags.unk_30)
-                        .with_unk_31(handle.flags.unk_31)
-                        .with_unk_32(handle.flags.unk_32),
-                    emitter_set_handle: handle.emitter_set_handle,
-                    effect_model_entry_handle: value
-                        .effect_model_entries
-                        .iter()
-                        .position(|model| model.name == handle.effect_model_name)
-                        .map_or(0, |i| i + 1) as i32,
-                    effect_group_element_start: if !handle.effect_group.is_empty() {
-                        let effect_group_element_start = effect_group_start_index + 1;
-
-                        effect_group_start_index += handle.effect_group.len() as i16;
-
-                        effect_group_element_start
-                    } else {
-                        0
-                    },
-                    effect_group_element_count: handle.effect_group.len() as i16,
-                })
-                .collect(),
-            effect_group_elements: value
-                .effect_handles
-                .iter()
-                .flat_map(|handle| {
-                    handle
-                        .effect_group
-                        .iter()
-                        .map(|element| EffectGroupElement {
-                            emitter_set_start_frame: element.emitter_set_start_frame,
-                            emitter_set_handle: element.emitter_set_handle,
-                        })
-                })
-                .collect(),
-            effect_model_entries: value
-                .effect_model_entries
-                .iter()
-                .map(|model| EffectModelEntry { unk: model.unk })
-                .collect(),
-            effect_handle_names: value
-                .effect_handles
-                .iter()
-                .map(|handle| handle.name.clone().into())
-                .collect(),
-            effect_model_names: value
-                .effect_model_entries
-                .iter()
-                .map(|model| model.name.clone().into())
-                .collect(),
-            parent_joint_names: value
-                .effect_handles
-                .iter()
-                .flat_map(|handle| {
-                    handle
-                        .effect_group
-                        .iter()
-                        .map(|element| element.parent_joint_name.clone().into())
-                })
-                .collect(),
-            resource_data: value.resource_data.clone(),
-        }
+        value.to_eff_file(GroupElementOrder::Packed)
     }
 }
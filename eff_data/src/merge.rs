@@ -0,0 +1,109 @@
+use crate::{DataError, EffData};
+
+/// How to resolve an entry of [`EffData::merge`] whose name already exists with different values.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum MergeStrategy {
+    /// Keep the existing entry.
+    #[default]
+    Skip,
+
+    /// Replace the existing entry in place.
+    Overwrite,
+
+    /// Return an error without merging anything.
+    Error,
+}
+
+impl EffData {
+    /// Appends the effect handles and effect model entries of `other`, resolving entries whose name already exists with `strategy`.
+    ///
+    /// Entries identical to an existing entry are never duplicated.
+    /// The contained file resource of `other` is not merged, so its effect handles are expected to reference emitter sets by the same indices in the resource of `self`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `strategy` is [`MergeStrategy::Error`] and an entry conflicts with an existing entry, in which case `self` is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData, MergeStrategy};
+    ///
+    /// let mut data = EffData::new();
+    /// data.add_effect_handle(EffectHandleData {
+    ///     name: "EFF_SMOKE".to_string(),
+    ///     emitter_set_handle: 1,
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let mut other = EffData::new();
+    /// for (name, emitter_set_handle) in [("EFF_SMOKE", 2), ("EFF_SPARK", 3)] {
+    ///     other
+    ///         .add_effect_handle(EffectHandleData {
+    ///             name: name.to_string(),
+    ///             emitter_set_handle,
+    ///             ..Default::default()
+    ///         })
+    ///         .unwrap();
+    /// }
+    ///
+    /// assert!(data.clone().merge(other.clone(), MergeStrategy::Error).is_err());
+    ///
+    /// data.merge(other, MergeStrategy::Overwrite).unwrap();
+    ///
+    /// assert_eq!(data.effect_handles.len(), 2);
+    /// assert_eq!(data.find_handle("EFF_SMOKE").unwrap().emitter_set_handle, 2);
+    /// ```
+    pub fn merge(&mut self, other: EffData, strategy: MergeStrategy) -> Result<(), DataError> {
+        if strategy == MergeStrategy::Error {
+            for handle in &other.effect_handles {
+                if self
+                    .find_handle(&handle.name)
+                    .is_some_and(|existing| existing != handle)
+                {
+                    return Err(DataError::DuplicateEffectHandle {
+                        name: handle.name.clone(),
+                    });
+                }
+            }
+
+            for model in &other.effect_model_entries {
+                if self
+                    .find_model_entry(&model.name)
+                    .is_some_and(|existing| existing != model)
+                {
+                    return Err(DataError::DuplicateEffectModel {
+                        name: model.name.clone(),
+                    });
+                }
+            }
+        }
+
+        for handle in other.effect_handles {
+            match self.find_handle_mut(&handle.name) {
+                Some(existing) => {
+                    if strategy == MergeStrategy::Overwrite {
+                        *existing = handle;
+                    }
+                }
+                None => self.effect_handles.push(handle),
+            }
+        }
+
+        for model in other.effect_model_entries {
+            match self.find_model_entry_mut(&model.name) {
+                Some(existing) => {
+                    if strategy == MergeStrategy::Overwrite {
+                        *existing = model;
+                    }
+                }
+                None => self.effect_model_entries.push(model),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{EffData, FlagNames};
+
+/// Annotations for an EFF file, stored in a `.eff.meta.json` sidecar file instead of the EFF file itself.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EffMeta {
+    /// User comment describing the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// User tags for the file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+
+    /// Names of the authors of the file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+
+    /// Annotations for each effect handle, keyed by effect handle name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub effect_handles: BTreeMap<String, EffectHandleMeta>,
+
+    /// User-supplied names for the effect handle flags.
+    #[serde(default, skip_serializing_if = "FlagNames::is_empty")]
+    pub flag_names: FlagNames,
+}
+
+/// Annotations for an effect handle, stored in an [`EffMeta`].
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct EffectHandleMeta {
+    /// User comment describing the effect handle.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+
+    /// User tags for the effect handle.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl EffMeta {
+    /// Returns the path of the sidecar file for the EFF file at the given path.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::path::Path;
+    ///
+    /// use eff_data::EffMeta;
+    ///
+    /// assert_eq!(
+    ///     EffMeta::sidecar_path("effect/fighter/mario/ef_mario.eff"),
+    ///     Path::new("effect/fighter/mario/ef_mario.eff.meta.json")
+    /// );
+    /// ```
+    pub fn sidecar_path<P: AsRef<Path>>(eff_path: P) -> PathBuf {
+        let mut path = eff_path.as_ref().as_os_str().to_owned();
+
+        path.push(".meta.json");
+        path.into()
+    }
+
+    /// Collects the annotations of the given data.
+    pub fn from_data(data: &EffData) -> Self {
+        Self {
+            comment: data.comment.clone(),
+            tags: data.tags.clone(),
+            authors: data.authors.clone(),
+            effect_handles: data
+                .effect_handles
+                .iter()
+                .filter(|handle| handle.comment.is_some() || !handle.tags.is_empty())
+                .map(|handle| {
+                    (
+                        handle.name.clone(),
+                        EffectHandleMeta {
+                            comment: handle.comment.clone(),
+                            tags: handle.tags.clone(),
+                        },
+                    )
+                })
+                .collect(),
+            flag_names: FlagNames::new(),
+        }
+    }
+
+    /// Returns `true` if there are no annotations, and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Reads the annotations from the given file path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Writes the annotations to the given file path.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Reads the sidecar file for the EFF file at the given path, returning `None` if it does not exist.
+    pub fn read_sidecar<P: AsRef<Path>>(eff_path: P) -> io::Result<Option<Self>> {
+        match Self::from_file(Self::sidecar_path(eff_path)) {
+            Ok(meta) => Ok(Some(meta)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Writes the sidecar file for the EFF file at the given path.
+    pub fn write_sidecar<P: AsRef<Path>>(&self, eff_path: P) -> io::Result<()> {
+        self.write_to_file(Self::sidecar_path(eff_path))
+    }
+}
+
+impl EffData {
+    /// Merges the given annotations into the data.
+    ///
+    /// Comments in the annotations replace existing comments, and tags and authors not already present are appended.
+    /// Annotations for effect handles which do not exist are ignored.
+    pub fn apply_meta(&mut self, meta: &EffMeta) {
+        if meta.comment.is_some() {
+            self.comment.clone_from(&meta.comment);
+        }
+
+        merge_unique(&mut self.tags, &meta.tags);
+        merge_unique(&mut self.authors, &meta.authors);
+
+        for handle in &mut self.effect_handles {
+            if let Some(handle_meta) = meta.effect_handles.get(&handle.name) {
+                if handle_meta.comment.is_some() {
+                    handle.comment.clone_from(&handle_meta.comment);
+                }
+
+                merge_unique(&mut handle.tags, &handle_meta.tags);
+            }
+        }
+    }
+}
+
+fn merge_unique(values: &mut Vec<String>, new_values: &[String]) {
+    for value in new_values {
+        if !values.contains(value) {
+            values.push(value.clone());
+        }
+    }
+}
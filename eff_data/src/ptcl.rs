@@ -0,0 +1,1238 @@
+use std::{
+    collections::HashMap,
+    io::{Read, Seek, Write},
+    mem,
+    path::Path,
+};
+
+use binrw::{BinResult, Endian};
+use eff_lib::{EmitterField, PtclFile, PtclHeader, PtclTexture, Section};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "resource")]
+use crate::ResourceData;
+use crate::{
+    emitter_set_index, DataError, EffData, EffectGroupElementData, EffectHandleData, ResourceKind,
+};
+
+const NAME_OFFSET: usize = 0x10;
+const NAME_LENGTH: usize = 0x40;
+
+/// The data associated with a [`PtclFile`].
+///
+/// Emitter sets and emitters are resolved into owned collections with decoded names, and every other section is kept in its binary form.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_data::{EmitterData, EmitterSetData, PtclData};
+/// use eff_lib::PtclFile;
+///
+/// let mut data = PtclData::default();
+///
+/// let mut emitter_set = EmitterSetData::new("smoke".to_string());
+///
+/// emitter_set.emitters.push(EmitterData::new("puff".to_string()));
+/// data.emitter_sets.push(emitter_set);
+///
+/// let data = PtclData::from(&PtclFile::from(&data));
+///
+/// assert_eq!(data.emitter_set(1).unwrap().name, "smoke");
+/// assert_eq!(data.emitter_sets[0].emitters[0].name, "puff");
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct PtclData {
+    /// Header of the resource.
+    pub header: PtclHeader,
+
+    /// Collection of emitter sets, in the order they are indexed by emitter set handles.
+    pub emitter_sets: Vec<EmitterSetData>,
+
+    /// Collection of the other top-level sections, such as textures and shaders.
+    pub sections: Vec<Section>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    emitter_set_array: Option<(usize, Template)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    file: Option<PtclFile>,
+}
+
+/// The positions emitter sets were moved to by [`PtclData::merge`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EmitterSetMapping {
+    indices: Vec<usize>,
+}
+
+/// The data associated with an emitter set section of a [`PtclFile`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct EmitterSetData {
+    /// Name of the emitter set.
+    pub name: String,
+
+    /// Data buffer for the binary data of the emitter set, including its name.
+    pub data: Vec<u8>,
+
+    /// Collection of emitters.
+    pub emitters: Vec<EmitterData>,
+
+    /// Collection of the other child sections.
+    pub children: Vec<Section>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    template: Option<Template>,
+}
+
+/// The data associated with an emitter section of a [`PtclFile`].
+///
+/// The blocks of the binary data are read and written in the little-endian layout used by Super Smash Bros. Ultimate.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct EmitterData {
+    /// Name of the emitter.
+    pub name: String,
+
+    /// Data buffer for the binary data of the emitter, including its name.
+    pub data: Vec<u8>,
+
+    /// Collection of child emitters spawned by the emitter.
+    pub emitters: Vec<EmitterData>,
+
+    /// Collection of the other child sections.
+    pub children: Vec<Section>,
+
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    template: Option<Template>,
+}
+
+impl PtclData {
+    /// Reads the data from the given file path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> BinResult<Self> {
+        Ok((&PtclFile::from_file(path)?).into())
+    }
+
+    /// Reads the data from the given byte slice, such as the resource data of an EFF file.
+    pub fn from_bytes(bytes: &[u8]) -> BinResult<Self> {
+        Ok((&PtclFile::from_bytes(bytes)?).into())
+    }
+
+    /// Reads the data from the given reader.
+    pub fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        Ok((&PtclFile::read(reader)?).into())
+    }
+
+    /// Writes the data to the given writer.
+    pub fn write<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
+        PtclFile::from(self).write(writer)
+    }
+
+    /// Writes the data to the given file path.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
+        PtclFile::from(self).write_to_file(path)
+    }
+
+    /// Writes the data to a new buffer.
+    pub fn to_bytes(&self) -> BinResult<Vec<u8>> {
+        PtclFile::from(self).to_bytes()
+    }
+
+    /// Returns the byte order of the resource, which is little-endian for data which was not read from a file.
+    pub fn endian(&self) -> Endian {
+        self.file
+            .as_ref()
+            .map_or(Endian::Little, |file| file.endian)
+    }
+
+    /// Returns the emitter set referenced by the given emitter set handle.
+    pub fn emitter_set(&self, handle: i32) -> Option<&EmitterSetData> {
+        self.emitter_sets.get(emitter_set_index(handle)?)
+    }
+
+    /// Returns the emitter set referenced by the given emitter set handle.
+    pub fn emitter_set_mut(&mut self, handle: i32) -> Option<&mut EmitterSetData> {
+        self.emitter_sets.get_mut(emitter_set_index(handle)?)
+    }
+
+    /// Returns the names of the emitter sets, where the name at index `i` is referenced by the emitter set handle `i + 1`.
+    pub fn emitter_set_names(&self) -> Vec<String> {
+        self.emitter_sets
+            .iter()
+            .map(|emitter_set| emitter_set.name.clone())
+            .collect()
+    }
+
+    /// Returns the emitter set handle referencing the first emitter set with the given name.
+    pub fn emitter_set_handle(&self, name: &str) -> Option<i32> {
+        self.emitter_sets
+            .iter()
+            .position(|emitter_set| emitter_set.name == name)
+            .map(|index| index as i32 + 1)
+    }
+
+    /// Appends a copy of the emitter set at the given position under a new name, returning the emitter set handle referencing the copy.
+    ///
+    /// Returns `None` if there is no emitter set at the given position.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EmitterSetData, PtclData};
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    ///
+    /// let handle = ptcl.duplicate_emitter_set(0, "smoke_c01".to_string());
+    ///
+    /// assert_eq!(handle, Some(2));
+    /// assert_eq!(ptcl.emitter_set_names(), ["smoke", "smoke_c01"]);
+    /// ```
+    pub fn duplicate_emitter_set(&mut self, index: usize, new_name: String) -> Option<i32> {
+        let mut emitter_set = self.emitter_sets.get(index)?.clone();
+
+        emitter_set.name = new_name;
+        self.emitter_sets.push(emitter_set);
+
+        Some(self.emitter_sets.len() as i32)
+    }
+
+    /// Extracts the emitter set at the given position into a standalone resource, for sharing a single effect between files.
+    ///
+    /// Emitters index the texture, shader, and primitive arrays, so every other section is kept as-is to keep those indices valid.
+    /// Returns `None` if there is no emitter set at the given position.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EmitterSetData, PtclData};
+    /// use eff_lib::Section;
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// let mut shader_array = Section::new(Section::SHADER_ARRAY, Vec::new());
+    ///
+    /// for data in [vec![1], vec![2]] {
+    ///     shader_array.children.push(Section::new(*b"BNSH", data));
+    /// }
+    ///
+    /// ptcl.sections.push(shader_array);
+    /// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    /// ptcl.emitter_sets.push(EmitterSetData::new("spark".to_string()));
+    ///
+    /// let extracted = ptcl.extract_emitter_set(1).unwrap();
+    ///
+    /// assert_eq!(extracted.emitter_set_names(), ["spark"]);
+    /// assert_eq!(extracted.sections[0].children.len(), 2);
+    /// ```
+    pub fn extract_emitter_set(&self, index: usize) -> Option<PtclData> {
+        let emitter_set = self.emitter_sets.get(index)?.clone();
+        let mut file = PtclFile::from(&Self {
+            header: self.header.clone(),
+            emitter_sets: vec![emitter_set],
+            sections: self.sections.clone(),
+            emitter_set_array: self.emitter_set_array.clone(),
+            file: self.file.clone(),
+        });
+
+        // Lay out the remaining sections anew instead of leaving gaps at their original offsets.
+        file.rebuild();
+
+        Some(file.into())
+    }
+
+    /// Appends every emitter set of another resource, returning the positions they were moved to.
+    ///
+    /// Textures, shaders, and primitives the other resource holds beyond the ones in this resource are appended, as with [`PtclData::inject`].
+    /// Effect handles of the other resource can be pointed at the merged emitter sets by passing [`EmitterSetMapping::handles`] to [`EffData::remap_emitter_set_handles`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a texture, shader, or primitive differs between the resources, as the indices of the merged emitters cannot be rewritten, or if the textures cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EmitterSetData, PtclData};
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    ///
+    /// let mut other = PtclData::default();
+    /// other.emitter_sets.push(EmitterSetData::new("spark".to_string()));
+    ///
+    /// let mapping = ptcl.merge(other).unwrap();
+    ///
+    /// assert_eq!(mapping.index(0), Some(1));
+    /// assert_eq!(mapping.handle(1), Some(2));
+    /// assert_eq!(mapping.handles(), [(1, 2)].into());
+    /// assert_eq!(ptcl.emitter_set_names(), ["smoke", "spark"]);
+    /// ```
+    pub fn merge(&mut self, other: PtclData) -> BinResult<EmitterSetMapping> {
+        let count = other.emitter_sets.len();
+        let start = self.import_emitter_sets(other)?;
+
+        Ok(EmitterSetMapping {
+            indices: (start..start + count).collect(),
+        })
+    }
+
+    /// Appends the emitter sets of a standalone resource, such as one created by [`PtclData::extract_emitter_set`], returning the emitter set handle referencing the first of them.
+    ///
+    /// The indices emitters use for textures, shaders, and primitives are not rewritten, so the package must hold the same resources as this one at the same positions.
+    /// Resources the package holds beyond the ones in this resource are appended, such as ones added to an extracted emitter set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a texture, shader, or primitive differs between the package and this resource, or if the textures cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EmitterSetData, PtclData};
+    /// use eff_lib::Section;
+    ///
+    /// let mut package = PtclData::default();
+    /// let mut primitive_array = Section::new(Section::PRIMITIVE_ARRAY, Vec::new());
+    /// primitive_array
+    ///     .children
+    ///     .push(Section::new(Section::PRIMITIVE, vec![1; 0x10]));
+    /// package.sections.push(primitive_array);
+    /// package.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// ptcl.emitter_sets.push(EmitterSetData::new("spark".to_string()));
+    ///
+    /// assert_eq!(ptcl.inject(package.clone()).unwrap(), 2);
+    /// assert_eq!(ptcl.inject(package.clone()).unwrap(), 3);
+    /// assert_eq!(ptcl.sections[0].children.len(), 1);
+    ///
+    /// package.sections[0].children[0].data = vec![2; 0x10];
+    ///
+    /// assert!(ptcl.inject(package).is_err());
+    /// ```
+    pub fn inject(&mut self, package: PtclData) -> BinResult<i32> {
+        let start = self.import_emitter_sets(package)?;
+
+        Ok(start as i32 + 1)
+    }
+
+    /// Appends the emitter sets of another resource along with the resources they use, returning the position of the first of them.
+    fn import_emitter_sets(&mut self, other: PtclData) -> BinResult<usize> {
+        let mut resources = Resources::read(self)?;
+        let counts = resources.counts();
+
+        resources.import(Resources::read(&other)?)?;
+
+        if resources.counts() != counts {
+            resources.write(self);
+        }
+
+        let start = self.emitter_sets.len();
+
+        self.emitter_sets.extend(other.emitter_sets);
+
+        Ok(start)
+    }
+
+    /// Discards the recorded placement of every section, so the resource is laid out anew without gaps when written.
+    fn rebuild(&mut self) {
+        let mut file = PtclFile::from(&*self);
+
+        file.rebuild();
+        *self = file.into();
+    }
+
+    /// Renames the emitter set at the given position, returning its previous name.
+    ///
+    /// The name is stored in a fixed-size field of the emitter set, so writing never moves any other data.
+    /// Names longer than 63 bytes are truncated when written.
+    ///
+    /// Returns `None` if there is no emitter set at the given position.
+    pub fn rename_emitter_set(&mut self, index: usize, new_name: String) -> Option<String> {
+        let emitter_set = self.emitter_sets.get_mut(index)?;
+
+        Some(mem::replace(&mut emitter_set.name, new_name))
+    }
+
+    /// Returns the emitter set with the given name.
+    pub fn emitter_set_by_name(&self, name: &str) -> Option<&EmitterSetData> {
+        self.emitter_sets
+            .iter()
+            .find(|emitter_set| emitter_set.name == name)
+    }
+}
+
+impl EffData {
+    /// Returns the name of the emitter set referenced by each effect handle, in effect handle order.
+    ///
+    /// Effect handles without an emitter set, or with one outside of the resource, resolve to `None`.
+    pub fn resolve_emitter_set_names<'a>(&self, ptcl: &'a PtclData) -> Vec<Option<&'a str>> {
+        self.effect_handles
+            .iter()
+            .map(|handle| handle.emitter_set_name(ptcl))
+            .collect()
+    }
+
+    /// Returns the indices of the emitter sets of the resource which are not referenced by any effect handle or effect group element.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EmitterSetData, PtclData};
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    ///
+    /// let data = EffData::new();
+    ///
+    /// assert_eq!(data.unused_emitter_sets(&ptcl), [0]);
+    /// ```
+    pub fn unused_emitter_sets(&self, ptcl: &PtclData) -> Vec<usize> {
+        let mut used = vec![false; ptcl.emitter_sets.len()];
+        let handles = self.effect_handles.iter().flat_map(|handle| {
+            std::iter::once(handle.emitter_set_handle).chain(
+                handle
+                    .effect_group
+                    .iter()
+                    .map(|element| element.emitter_set_handle as i32),
+            )
+        });
+
+        for index in handles.filter_map(emitter_set_index) {
+            if let Some(used) = used.get_mut(index) {
+                *used = true;
+            }
+        }
+
+        (0..used.len()).filter(|&i| !used[i]).collect()
+    }
+
+    /// Appends an emitter set to the resource, returning the emitter set handle referencing it.
+    pub fn insert_emitter_set(
+        &mut self,
+        ptcl: &mut PtclData,
+        emitter_set: EmitterSetData,
+    ) -> Result<i32, DataError> {
+        self.insert_emitter_set_at(ptcl, ptcl.emitter_sets.len(), emitter_set)
+    }
+
+    /// Inserts an emitter set into the resource at the given position, returning the emitter set handle referencing it.
+    ///
+    /// References to the emitter sets after it are shifted to keep pointing at the same emitter sets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a shifted reference is out of range, in which case nothing is changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of emitter sets.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EmitterSetData, PtclData};
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    ///
+    /// let mut data = EffData::new();
+    /// let handle = data
+    ///     .insert_emitter_set_at(&mut ptcl, 0, EmitterSetData::new("spark".to_string()))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(handle, 1);
+    /// assert_eq!(ptcl.emitter_set_names(), ["spark", "smoke"]);
+    /// ```
+    pub fn insert_emitter_set_at(
+        &mut self,
+        ptcl: &mut PtclData,
+        index: usize,
+        emitter_set: EmitterSetData,
+    ) -> Result<i32, DataError> {
+        assert!(
+            index <= ptcl.emitter_sets.len(),
+            "emitter set index {index} is out of bounds"
+        );
+
+        let handle = index as i32 + 1;
+
+        self.shift_emitter_set_handles(handle, 1)?;
+        ptcl.emitter_sets.insert(index, emitter_set);
+
+        Ok(handle)
+    }
+
+    /// Removes the emitter set at the given position from the resource, returning it.
+    ///
+    /// References to the emitter sets after it are shifted to keep pointing at the same emitter sets.
+    /// If an effect handle or effect group element still references the emitter set, an error is returned unless `force` is `true`, in which case the references are cleared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove_emitter_set(
+        &mut self,
+        ptcl: &mut PtclData,
+        index: usize,
+        force: bool,
+    ) -> Result<EmitterSetData, DataError> {
+        assert!(
+            index < ptcl.emitter_sets.len(),
+            "emitter set index {index} is out of bounds"
+        );
+
+        let handle = index as i32 + 1;
+
+        for effect_handle in &self.effect_handles {
+            let references = effect_handle.emitter_set_handle == handle
+                || effect_handle
+                    .effect_group
+                    .iter()
+                    .any(|element| element.emitter_set_handle as i32 == handle);
+
+            if references && !force {
+                return Err(DataError::EmitterSetInUse {
+                    emitter_set_handle: handle,
+                    effect_handle_name: effect_handle.name.clone(),
+                });
+            }
+        }
+
+        for effect_handle in &mut self.effect_handles {
+            if effect_handle.emitter_set_handle == handle {
+                effect_handle.emitter_set_handle = 0;
+            }
+
+            for element in &mut effect_handle.effect_group {
+                if element.emitter_set_handle as i32 == handle {
+                    element.emitter_set_handle = 0;
+                }
+            }
+        }
+
+        self.shift_emitter_set_handles(handle + 1, -1)?;
+
+        Ok(ptcl.emitter_sets.remove(index))
+    }
+
+    /// Removes every emitter set which is not referenced by an effect handle or effect group element, returning the removed emitter sets in their previous order.
+    ///
+    /// References to the remaining emitter sets are renumbered to keep pointing at the same emitter sets.
+    /// If any emitter set is removed, the resource is laid out anew so the space they used is reclaimed.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData, EmitterSetData, PtclData};
+    ///
+    /// let mut ptcl = PtclData::default();
+    ///
+    /// for name in ["smoke", "spark"] {
+    ///     let mut emitter_set = EmitterSetData::new(name.to_string());
+    ///     emitter_set.data = vec![0; 0x100];
+    ///     ptcl.emitter_sets.push(emitter_set);
+    /// }
+    ///
+    /// let mut ptcl = PtclData::from_bytes(&ptcl.to_bytes().unwrap()).unwrap();
+    /// let size = ptcl.to_bytes().unwrap().len();
+    ///
+    /// let mut data = EffData::new();
+    /// data.add_effect_handle(EffectHandleData {
+    ///     name: "EFF_SPARK".to_string(),
+    ///     emitter_set_handle: 2,
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let removed = data.strip_unused_resource(&mut ptcl);
+    ///
+    /// assert_eq!(removed[0].name, "smoke");
+    /// assert_eq!(ptcl.emitter_set_names(), ["spark"]);
+    /// assert_eq!(data.effect_handles[0].emitter_set_handle, 1);
+    /// assert!(ptcl.to_bytes().unwrap().len() < size);
+    /// ```
+    pub fn strip_unused_resource(&mut self, ptcl: &mut PtclData) -> Vec<EmitterSetData> {
+        let mut removed = Vec::new();
+
+        for index in self.unused_emitter_sets(ptcl).into_iter().rev() {
+            // Unused emitter sets have no references, so removing them never fails.
+            if let Ok(emitter_set) = self.remove_emitter_set(ptcl, index, false) {
+                removed.push(emitter_set);
+            }
+        }
+
+        if !removed.is_empty() {
+            ptcl.rebuild();
+        }
+
+        removed.reverse();
+        removed
+    }
+
+    /// Copies the effect handles with the given names into a new EFF file, along with a copy of the resource trimmed to what they use.
+    ///
+    /// Only the effect model entries referenced by the copied effect handles are kept.
+    /// Emitter sets which are not referenced are removed from the copied resource.
+    /// Textures, shaders, and primitives are all kept, as the indices of the emitters referencing them are not rewritten.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no effect handle with one of the given names.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData, EmitterSetData, PtclData};
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    /// ptcl.emitter_sets.push(EmitterSetData::new("spark".to_string()));
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for (name, emitter_set_handle) in [("EFF_SMOKE", 1), ("EFF_SPARK", 2)] {
+    ///     data.effect_handles.push(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         emitter_set_handle,
+    ///         ..Default::default()
+    ///     });
+    /// }
+    ///
+    /// let (subset, subset_ptcl) = data.extract_handles(&ptcl, &["EFF_SPARK"]).unwrap();
+    ///
+    /// assert_eq!(subset.effect_handles.len(), 1);
+    /// assert_eq!(subset.effect_handles[0].emitter_set_handle, 1);
+    /// assert_eq!(subset_ptcl.emitter_set_names(), ["spark"]);
+    /// ```
+    pub fn extract_handles(&self, ptcl: &PtclData, names: &[&str]) -> BinResult<(Self, PtclData)> {
+        let effect_handles = names
+            .iter()
+            .map(|name| {
+                self.find_handle(name)
+                    .cloned()
+                    .ok_or_else(|| DataError::UnknownEffectHandle {
+                        name: name.to_string(),
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let effect_model_entries = self
+            .effect_model_entries
+            .iter()
+            .filter(|model| {
+                effect_handles
+                    .iter()
+                    .any(|handle| handle.effect_model_name == model.name)
+            })
+            .cloned()
+            .collect();
+        let mut data = Self {
+            effect_handles,
+            effect_model_entries,
+            #[cfg(feature = "resource")]
+            resource_data: ResourceData::None,
+            comment: self.comment.clone(),
+            tags: self.tags.clone(),
+            authors: self.authors.clone(),
+        };
+        let mut ptcl = ptcl.clone();
+
+        data.strip_unused_resource(&mut ptcl);
+
+        Ok((data, ptcl))
+    }
+}
+
+impl EffectHandleData {
+    /// Returns the name of the emitter set referenced by the effect handle.
+    pub fn emitter_set_name<'a>(&self, ptcl: &'a PtclData) -> Option<&'a str> {
+        ptcl.emitter_set(self.emitter_set_handle)
+            .map(|emitter_set| emitter_set.name.as_str())
+    }
+
+    /// Points the effect handle at the first emitter set with the given name.
+    ///
+    /// Returns `true` if the emitter set was found, and `false` otherwise.
+    pub fn set_emitter_set_name(&mut self, ptcl: &PtclData, name: &str) -> bool {
+        match ptcl.emitter_set_handle(name) {
+            Some(handle) => {
+                self.emitter_set_handle = handle;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl EffectGroupElementData {
+    /// Returns the name of the emitter set referenced by the effect group element.
+    pub fn emitter_set_name<'a>(&self, ptcl: &'a PtclData) -> Option<&'a str> {
+        ptcl.emitter_set(self.emitter_set_handle as i32)
+            .map(|emitter_set| emitter_set.name.as_str())
+    }
+
+    /// Points the effect group element at the first emitter set with the given name.
+    ///
+    /// Returns `true` if the emitter set was found and its handle fits in the element, and `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffectGroupElementData, EmitterSetData, PtclData};
+    ///
+    /// let mut ptcl = PtclData::default();
+    ///
+    /// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+    /// ptcl.emitter_sets.push(EmitterSetData::new("spark".to_string()));
+    ///
+    /// let mut element = EffectGroupElementData {
+    ///     emitter_set_start_frame: 0,
+    ///     emitter_set_handle: 0,
+    ///     parent_joint_name: "top".to_string(),
+    ///     original_index: None,
+    /// };
+    ///
+    /// assert!(element.set_emitter_set_name(&ptcl, "spark"));
+    /// assert_eq!(element.emitter_set_handle, 2);
+    /// assert_eq!(element.emitter_set_name(&ptcl), Some("spark"));
+    /// assert!(!element.set_emitter_set_name(&ptcl, "fire"));
+    /// ```
+    pub fn set_emitter_set_name(&mut self, ptcl: &PtclData, name: &str) -> bool {
+        match ptcl
+            .emitter_set_handle(name)
+            .and_then(|handle| i16::try_from(handle).ok())
+        {
+            Some(handle) => {
+                self.emitter_set_handle = handle;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl PartialEq for PtclData {
+    fn eq(&self, other: &Self) -> bool {
+        self.header == other.header
+            && self.emitter_sets == other.emitter_sets
+            && self.sections == other.sections
+    }
+}
+
+impl From<&PtclFile> for PtclData {
+    fn from(value: &PtclFile) -> Self {
+        let mut sections = Vec::new();
+        let mut emitter_sets = Vec::new();
+        let mut emitter_set_array = None;
+
+        for section in &value.sections {
+            if section.magic != Section::EMITTER_SET_ARRAY || emitter_set_array.is_some() {
+                sections.push(section.clone());
+                continue;
+            }
+
+            let (children, template) = Template::split(section.clone(), &Section::EMITTER_SET);
+
+            emitter_sets = children.into_iter().map(EmitterSetData::from).collect();
+            emitter_set_array = Some((sections.len(), template));
+        }
+
+        let mut file = value.clone();
+
+        file.sections.clear();
+
+        Self {
+            header: value.header.clone(),
+            emitter_sets,
+            sections,
+            emitter_set_array,
+            file: Some(file),
+        }
+    }
+}
+
+impl From<PtclFile> for PtclData {
+    fn from(value: PtclFile) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&PtclData> for PtclFile {
+    fn from(value: &PtclData) -> Self {
+        let mut file = value
+            .file
+            .clone()
+            .unwrap_or_else(|| PtclFile::new(value.header.clone()));
+        let emitter_sets = value.emitter_sets.iter().map(Section::from);
+        let (index, emitter_set_array) = match &value.emitter_set_array {
+            Some((index, template)) => (*index, template.merge(emitter_sets, &template.others)),
+            None => (
+                0,
+                Template::new(Section::EMITTER_SET_ARRAY).merge(emitter_sets, &[]),
+            ),
+        };
+
+        file.header = value.header.clone();
+        file.sections = value.sections.clone();
+        file.sections
+            .insert(index.min(file.sections.len()), emitter_set_array);
+
+        file
+    }
+}
+
+impl From<PtclData> for PtclFile {
+    fn from(value: PtclData) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl EmitterSetData {
+    /// Constructs a new emitter set with the given name and no emitters.
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Returns an iterator over the emitters and their child emitters, visiting each emitter before its children.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EmitterData, EmitterSetData};
+    ///
+    /// let mut parent = EmitterData::new("parent".to_string());
+    /// parent.emitters.push(EmitterData::new("child".to_string()));
+    ///
+    /// let mut emitter_set = EmitterSetData::new("smoke".to_string());
+    /// emitter_set.emitters.push(parent);
+    /// emitter_set.emitters.push(EmitterData::new("sibling".to_string()));
+    ///
+    /// let emitters = emitter_set
+    ///     .emitters_depth_first()
+    ///     .map(|(depth, emitter)| (depth, emitter.name.as_str()))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(emitters, [(0, "parent"), (1, "child"), (0, "sibling")]);
+    /// ```
+    pub fn emitters_depth_first(&self) -> EmittersDepthFirst<'_> {
+        EmittersDepthFirst {
+            stack: self
+                .emitters
+                .iter()
+                .rev()
+                .map(|emitter| (0, emitter))
+                .collect(),
+        }
+    }
+}
+
+impl EmitterSetMapping {
+    /// Returns the new position of the emitter set at the given previous position.
+    pub fn index(&self, index: usize) -> Option<usize> {
+        self.indices.get(index).copied()
+    }
+
+    /// Returns the new emitter set handle for the given previous emitter set handle.
+    pub fn handle(&self, handle: i32) -> Option<i32> {
+        self.index(emitter_set_index(handle)?)
+            .map(|index| index as i32 + 1)
+    }
+
+    /// Returns the new emitter set handle of every moved emitter set, keyed by its previous emitter set handle.
+    pub fn handles(&self) -> HashMap<i32, i32> {
+        self.indices
+            .iter()
+            .enumerate()
+            .map(|(index, new_index)| (index as i32 + 1, *new_index as i32 + 1))
+            .collect()
+    }
+
+    /// Returns the number of emitter sets which were moved.
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if no emitter sets were moved, and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+/// An iterator over the emitters of an [`EmitterSetData`] and their child emitters, in depth-first order.
+///
+/// Each emitter is returned with its depth, where the emitters of the emitter set have a depth of `0`.
+#[derive(Debug, Clone)]
+pub struct EmittersDepthFirst<'a> {
+    stack: Vec<(usize, &'a EmitterData)>,
+}
+
+impl<'a> Iterator for EmittersDepthFirst<'a> {
+    type Item = (usize, &'a EmitterData);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, emitter) = self.stack.pop()?;
+
+        self.stack.extend(
+            emitter
+                .emitters
+                .iter()
+                .rev()
+                .map(|child| (depth + 1, child)),
+        );
+
+        Some((depth, emitter))
+    }
+}
+
+impl PartialEq for EmitterSetData {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.data == other.data
+            && self.emitters == other.emitters
+            && self.children == other.children
+    }
+}
+
+impl From<Section> for EmitterSetData {
+    fn from(value: Section) -> Self {
+        let (emitters, mut template) = Template::split(value, &Section::EMITTER);
+        let data = mem::take(&mut template.section.data);
+
+        Self {
+            name: name(&data),
+            data,
+            emitters: emitters.into_iter().map(EmitterData::from).collect(),
+            children: mem::take(&mut template.others),
+            template: Some(template),
+        }
+    }
+}
+
+impl From<&EmitterSetData> for Section {
+    fn from(value: &EmitterSetData) -> Self {
+        let emitters = value.emitters.iter().map(Section::from);
+        let mut section = match &value.template {
+            Some(template) => template.merge(emitters, &value.children),
+            None => Template::new(Section::EMITTER_SET).merge(emitters, &value.children),
+        };
+
+        section.data = with_name(&value.data, &value.name);
+
+        section
+    }
+}
+
+impl EmitterData {
+    /// Constructs a new emitter with the given name.
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    /// Reads the fields of the emitter, in the order of its child sections.
+    pub fn fields(&self) -> BinResult<Vec<EmitterField>> {
+        self.children
+            .iter()
+            .filter_map(|section| EmitterField::from_section(section).transpose())
+            .collect()
+    }
+
+    /// Writes the field into the first child section of the same kind, or appends a new child section if there is none.
+    pub fn set_field(&mut self, field: &EmitterField) -> BinResult<()> {
+        match self
+            .children
+            .iter_mut()
+            .find(|section| section.magic == field.magic())
+        {
+            Some(section) => field.write_to_section_data(&mut section.data),
+            None => {
+                self.children.push(field.to_section()?);
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl PartialEq for EmitterData {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.data == other.data
+            && self.emitters == other.emitters
+            && self.children == other.children
+    }
+}
+
+impl From<Section> for EmitterData {
+    fn from(value: Section) -> Self {
+        let (emitters, mut template) = Template::split(value, &Section::EMITTER);
+        let data = mem::take(&mut template.section.data);
+
+        Self {
+            name: name(&data),
+            data,
+            emitters: emitters.into_iter().map(EmitterData::from).collect(),
+            children: mem::take(&mut template.others),
+            template: Some(template),
+        }
+    }
+}
+
+impl From<&EmitterData> for Section {
+    fn from(value: &EmitterData) -> Self {
+        let emitters = value.emitters.iter().map(Section::from);
+        let mut section = match &value.template {
+            Some(template) => template.merge(emitters, &value.children),
+            None => Template::new(Section::EMITTER).merge(emitters, &value.children),
+        };
+
+        section.data = with_name(&value.data, &value.name);
+
+        section
+    }
+}
+
+/// A section as it was read, used to write it back with the same metadata and order of children.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+struct Template {
+    section: Section,
+    others: Vec<Section>,
+    order: Vec<bool>,
+}
+
+impl Template {
+    fn new(magic: [u8; 4]) -> Self {
+        Self {
+            section: Section::new(magic, Vec::new()),
+            others: Vec::new(),
+            order: Vec::new(),
+        }
+    }
+
+    /// Splits the children with the given identifier from every other child, keeping the section as the template.
+    fn split(mut section: Section, magic: &[u8; 4]) -> (Vec<Section>, Self) {
+        let mut items = Vec::new();
+        let mut others = Vec::new();
+        let mut order = Vec::new();
+
+        for child in mem::take(&mut section.children) {
+            order.push(&child.magic == magic);
+
+            if &child.magic == magic {
+                items.push(child);
+            } else {
+                others.push(child);
+            }
+        }
+
+        let template = Self {
+            section,
+            others,
+            order,
+        };
+
+        (items, template)
+    }
+
+    /// Rebuilds the section with the given children, interleaving them in the order they were read.
+    ///
+    /// Children beyond those which were read are appended, with items before every other child.
+    fn merge<I: Iterator<Item = Section>>(&self, mut items: I, others: &[Section]) -> Section {
+        let mut section = self.section.clone();
+        let mut others = others.iter().cloned();
+
+        for is_item in &self.order {
+            let child = if *is_item {
+                items.next()
+            } else {
+                others.next()
+            };
+
+            section.children.extend(child);
+        }
+
+        section.children.extend(items);
+        section.children.extend(others);
+        section
+    }
+}
+
+/// The textures, shaders, and primitives of a resource, in the order they are indexed by emitters.
+struct Resources {
+    textures: Vec<PtclTexture>,
+    shaders: Vec<Section>,
+    primitives: Vec<Section>,
+}
+
+impl Resources {
+    fn read(ptcl: &PtclData) -> BinResult<Self> {
+        let children = |magic: &[u8; 4], child_magic: Option<&[u8; 4]>| {
+            ptcl.sections
+                .iter()
+                .find(|section| &section.magic == magic)
+                .into_iter()
+                .flat_map(|section| &section.children)
+                .filter(|child| child_magic.is_none_or(|child_magic| &child.magic == child_magic))
+                .cloned()
+                .collect()
+        };
+
+        Ok(Self {
+            textures: ptcl.textures()?,
+            shaders: children(&Section::SHADER_ARRAY, None),
+            primitives: children(&Section::PRIMITIVE_ARRAY, Some(&Section::PRIMITIVE)),
+        })
+    }
+
+    fn counts(&self) -> [usize; 3] {
+        [
+            self.textures.len(),
+            self.shaders.len(),
+            self.primitives.len(),
+        ]
+    }
+
+    /// Appends the resources `other` holds beyond the ones in this resource.
+    ///
+    /// The indices emitters use are not rewritten, so the resources both hold must be identical for the indices of either to stay valid.
+    fn import(&mut self, other: Self) -> Result<(), DataError> {
+        import(&mut self.textures, other.textures, ResourceKind::Texture)?;
+        import(&mut self.shaders, other.shaders, ResourceKind::Shader)?;
+        import(
+            &mut self.primitives,
+            other.primitives,
+            ResourceKind::Primitive,
+        )
+    }
+
+    /// Replaces the textures, shaders, and primitives of the resource, adding the arrays it is missing, and lays out the resource anew.
+    fn write(self, ptcl: &mut PtclData) {
+        let mut file = PtclFile::from(&*ptcl);
+
+        file.set_textures(&self.textures);
+
+        match file.section_mut(&Section::SHADER_ARRAY) {
+            Some(section) => section.children = self.shaders,
+            None if !self.shaders.is_empty() => {
+                let mut section = Section::new(Section::SHADER_ARRAY, Vec::new());
+
+                section.children = self.shaders;
+                file.sections.push(section);
+            }
+            None => {}
+        }
+
+        match file.section_mut(&Section::PRIMITIVE_ARRAY) {
+            Some(section) => {
+                let (_, template) = Template::split(section.clone(), &Section::PRIMITIVE);
+
+                *section = template.merge(self.primitives.into_iter(), &template.others);
+            }
+            None if !self.primitives.is_empty() => {
+                let mut section = Section::new(Section::PRIMITIVE_ARRAY, Vec::new());
+
+                section.children = self.primitives;
+                file.sections.push(section);
+            }
+            None => {}
+        }
+
+        file.rebuild();
+        *ptcl = file.into();
+    }
+}
+
+/// Appends the items beyond the ones already present, returning an error if an item present in both differs.
+fn import<T: PartialEq>(
+    items: &mut Vec<T>,
+    others: Vec<T>,
+    kind: ResourceKind,
+) -> Result<(), DataError> {
+    if let Some(index) = items
+        .iter()
+        .zip(&others)
+        .position(|(item, other)| item != other)
+    {
+        return Err(DataError::ResourceMismatch { kind, index });
+    }
+
+    let count = items.len();
+
+    items.extend(others.into_iter().skip(count));
+
+    Ok(())
+}
+
+/// Decodes the nul-padded name stored in the binary data of an emitter set or emitter.
+fn name(data: &[u8]) -> String {
+    let bytes = data
+        .get(NAME_OFFSET..(NAME_OFFSET + NAME_LENGTH).min(data.len()))
+        .unwrap_or_default();
+    let length = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+
+    String::from_utf8_lossy(&bytes[..length]).into_owned()
+}
+
+/// Returns a copy of the binary data with the given name stored in it, leaving the data untouched if it already holds the name.
+///
+/// Names which do not fit in the name field, including its nul terminator, are truncated.
+fn with_name(data: &[u8], new_name: &str) -> Vec<u8> {
+    let mut data = data.to_vec();
+
+    if name(&data) == new_name {
+        return data;
+    }
+
+    let mut length = new_name.len().min(NAME_LENGTH - 1);
+
+    while !new_name.is_char_boundary(length) {
+        length -= 1;
+    }
+
+    if data.len() < NAME_OFFSET + NAME_LENGTH {
+        data.resize(NAME_OFFSET + NAME_LENGTH, 0);
+    }
+
+    data[NAME_OFFSET..NAME_OFFSET + NAME_LENGTH].fill(0);
+    data[NAME_OFFSET..NAME_OFFSET + length].copy_from_slice(&new_name.as_bytes()[..length]);
+
+    data
+}
@@ -0,0 +1,139 @@
+use std::borrow::Cow;
+
+use binrw::BinResult;
+
+use crate::PtclData;
+
+/// The file resource contained in an [`EffData`](crate::EffData).
+///
+/// Resources are only parsed when requested, and unparsed resources are written back exactly as they were read.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_data::{EmitterSetData, PtclData, ResourceData};
+///
+/// let mut ptcl = PtclData::default();
+/// ptcl.emitter_sets.push(EmitterSetData::new("smoke".to_string()));
+///
+/// let mut resource = ResourceData::Raw(ptcl.to_bytes().unwrap());
+///
+/// assert!(resource.ptcl().is_none());
+///
+/// let ptcl = resource.parse().unwrap().unwrap();
+///
+/// assert_eq!(ptcl.emitter_set_names(), ["smoke"]);
+/// assert!(resource.ptcl().is_some());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub enum ResourceData {
+    /// No resource.
+    #[default]
+    None,
+
+    /// Data buffer for the unparsed resource.
+    Raw(Vec<u8>),
+
+    /// The parsed PTCL resource.
+    Ptcl(Box<PtclData>),
+}
+
+impl ResourceData {
+    /// Returns `true` if there is no resource, and `false` otherwise.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// Returns `true` if there is a resource, and `false` otherwise.
+    pub fn is_some(&self) -> bool {
+        !self.is_none()
+    }
+
+    /// Returns the unparsed binary data of the resource, if it has not been parsed.
+    pub fn raw(&self) -> Option<&[u8]> {
+        match self {
+            Self::Raw(data) => Some(data),
+            _ => None,
+        }
+    }
+
+    /// Returns the parsed resource, if it has been parsed.
+    pub fn ptcl(&self) -> Option<&PtclData> {
+        match self {
+            Self::Ptcl(ptcl) => Some(ptcl.as_ref()),
+            _ => None,
+        }
+    }
+
+    /// Returns the parsed resource, if it has been parsed.
+    pub fn ptcl_mut(&mut self) -> Option<&mut PtclData> {
+        match self {
+            Self::Ptcl(ptcl) => Some(ptcl.as_mut()),
+            _ => None,
+        }
+    }
+
+    /// Parses the resource if it has not been parsed yet, returning the parsed resource if there is one.
+    ///
+    /// The resource is left unparsed if it fails to parse.
+    pub fn parse(&mut self) -> BinResult<Option<&mut PtclData>> {
+        if let Self::Raw(data) = self {
+            *self = Self::Ptcl(Box::new(PtclData::from_bytes(data)?));
+        }
+
+        Ok(self.ptcl_mut())
+    }
+
+    /// Returns the binary data of the resource if there is one, writing it if it has been parsed.
+    pub fn to_bytes(&self) -> BinResult<Option<Cow<'_, [u8]>>> {
+        match self {
+            Self::None => Ok(None),
+            Self::Raw(data) => Ok(Some(Cow::Borrowed(data))),
+            Self::Ptcl(ptcl) => ptcl.to_bytes().map(|data| Some(Cow::Owned(data))),
+        }
+    }
+
+    /// Converts the resource into its binary data if there is one, writing it if it has been parsed.
+    pub fn into_bytes(self) -> BinResult<Option<Vec<u8>>> {
+        match self {
+            Self::None => Ok(None),
+            Self::Raw(data) => Ok(Some(data)),
+            Self::Ptcl(ptcl) => ptcl.to_bytes().map(Some),
+        }
+    }
+}
+
+impl PartialEq for ResourceData {
+    /// Compares two resources, comparing an unparsed resource to a parsed resource by the binary data it is written as.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::None, Self::None) => true,
+            (Self::Raw(data), Self::Raw(other)) => data == other,
+            (Self::Ptcl(ptcl), Self::Ptcl(other)) => ptcl == other,
+            (Self::Raw(data), Self::Ptcl(ptcl)) | (Self::Ptcl(ptcl), Self::Raw(data)) => {
+                ptcl.to_bytes().is_ok_and(|bytes| bytes == *data)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<Option<Vec<u8>>> for ResourceData {
+    fn from(value: Option<Vec<u8>>) -> Self {
+        value.map_or(Self::None, Self::Raw)
+    }
+}
+
+impl From<Vec<u8>> for ResourceData {
+    fn from(value: Vec<u8>) -> Self {
+        Self::Raw(value)
+    }
+}
+
+impl From<PtclData> for ResourceData {
+    fn from(value: PtclData) -> Self {
+        Self::Ptcl(Box::new(value))
+    }
+}
@@ -0,0 +1,309 @@
+use std::{fmt, mem, path::Path};
+
+use binrw::BinResult;
+
+use crate::EffData;
+
+/// An editing session over an [`EffData`], recording edits for undo and redo.
+///
+/// Each edit records a snapshot of the data from before it was made, so the number of recorded edits is bounded by a history limit.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_data::{EffData, EffSession, ResourceData};
+///
+/// let data = EffData::new();
+/// let mut session = EffSession::new(data);
+///
+/// session.edit("Attach resource", |data| data.resource_data = ResourceData::Raw(vec![0; 4]));
+/// assert!(session.is_dirty());
+///
+/// assert_eq!(session.undo(), Some("Attach resource"));
+/// assert!(session.data().resource_data.is_none());
+/// assert!(!session.is_dirty());
+///
+/// session.redo();
+/// assert!(session.data().resource_data.is_some());
+/// ```
+pub struct EffSession {
+    data: EffData,
+    revision: u64,
+    next_revision: u64,
+    saved_revision: Option<u64>,
+    undo_stack: Vec<Revision>,
+    redo_stack: Vec<Revision>,
+    history_limit: usize,
+    listeners: Vec<(ListenerId, Box<Listener>)>,
+    next_listener_id: u64,
+}
+
+type Listener = dyn FnMut(&SessionEvent, &EffSession);
+
+/// A change made to the data of an [`EffSession`], reported to its listeners.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionEvent {
+    /// An edit was applied with the given label.
+    Edited(String),
+
+    /// The edit with the given label was reverted.
+    Undone(String),
+
+    /// The edit with the given label was reapplied.
+    Redone(String),
+
+    /// The current data was marked as saved.
+    Saved,
+}
+
+/// Identifier of a listener added to an [`EffSession`], used to remove it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+#[derive(Debug, Clone)]
+struct Revision {
+    label: String,
+    data: EffData,
+    revision: u64,
+}
+
+impl EffSession {
+    const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+    /// Constructs a new session over the given data, which is considered saved.
+    pub fn new(data: EffData) -> Self {
+        Self {
+            data,
+            revision: 0,
+            next_revision: 1,
+            saved_revision: Some(0),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            history_limit: Self::DEFAULT_HISTORY_LIMIT,
+            listeners: Vec::new(),
+            next_listener_id: 0,
+        }
+    }
+
+    /// Reads the data from the given file path and starts a session over it.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> BinResult<Self> {
+        Ok(Self::new(EffData::from_file(path)?))
+    }
+
+    /// Returns a reference to the current data.
+    pub fn data(&self) -> &EffData {
+        &self.data
+    }
+
+    /// Ends the session, returning the current data.
+    pub fn into_data(self) -> EffData {
+        self.data
+    }
+
+    /// Sets the maximum number of edits which can be undone, discarding the oldest edits beyond it.
+    pub fn set_history_limit(&mut self, history_limit: usize) {
+        self.history_limit = history_limit;
+        self.trim_history();
+    }
+
+    /// Adds a listener which is called after every change to the session, returning an identifier to remove it with.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::{cell::RefCell, rc::Rc};
+    ///
+    /// use eff_data::{EffData, EffSession, ResourceData, SessionEvent};
+    ///
+    /// let data = EffData::new();
+    /// let mut session = EffSession::new(data);
+    /// let events = Rc::new(RefCell::new(Vec::new()));
+    /// let listener_events = Rc::clone(&events);
+    ///
+    /// let id = session.add_listener(move |event, session| {
+    ///     listener_events.borrow_mut().push((event.clone(), session.is_dirty()));
+    /// });
+    ///
+    /// session.edit("Clear resource", |data| data.resource_data = ResourceData::None);
+    /// session.undo();
+    /// session.remove_listener(id);
+    /// session.redo();
+    ///
+    /// assert_eq!(
+    ///     *events.borrow(),
+    ///     [
+    ///         (SessionEvent::Edited("Clear resource".to_string()), true),
+    ///         (SessionEvent::Undone("Clear resource".to_string()), false),
+    ///     ]
+    /// );
+    /// ```
+    pub fn add_listener<F>(&mut self, listener: F) -> ListenerId
+    where
+        F: FnMut(&SessionEvent, &EffSession) + 'static,
+    {
+        let id = ListenerId(self.next_listener_id);
+
+        self.next_listener_id += 1;
+        self.listeners.push((id, Box::new(listener)));
+
+        id
+    }
+
+    /// Removes the listener with the given identifier, returning `true` if it was found, and `false` otherwise.
+    pub fn remove_listener(&mut self, id: ListenerId) -> bool {
+        let len = self.listeners.len();
+
+        self.listeners.retain(|(listener_id, _)| *listener_id != id);
+        self.listeners.len() != len
+    }
+
+    /// Applies an edit to the data, recording it under the given label so it can be undone.
+    ///
+    /// Any edits which were undone can no longer be redone.
+    pub fn edit<S, F, R>(&mut self, label: S, edit: F) -> R
+    where
+        S: Into<String>,
+        F: FnOnce(&mut EffData) -> R,
+    {
+        let label = label.into();
+        let revision = Revision {
+            label: label.clone(),
+            data: self.data.clone(),
+            revision: self.revision,
+        };
+        let result = edit(&mut self.data);
+
+        self.undo_stack.push(revision);
+        self.redo_stack.clear();
+        self.revision = self.next_revision;
+        self.next_revision += 1;
+        self.trim_history();
+        self.notify(SessionEvent::Edited(label));
+
+        result
+    }
+
+    /// Reverts the most recent edit, returning its label if there was one.
+    pub fn undo(&mut self) -> Option<&str> {
+        let revision = self.undo_stack.pop()?;
+        let label = revision.label.clone();
+
+        let previous = self.swap_revision(revision, label);
+
+        self.redo_stack.push(previous);
+        self.notify(SessionEvent::Undone(
+            self.redo_stack.last().unwrap().label.clone(),
+        ));
+        self.redo_stack
+            .last()
+            .map(|revision| revision.label.as_str())
+    }
+
+    /// Reapplies the most recently undone edit, returning its label if there was one.
+    pub fn redo(&mut self) -> Option<&str> {
+        let revision = self.redo_stack.pop()?;
+        let label = revision.label.clone();
+
+        let previous = self.swap_revision(revision, label);
+
+        self.undo_stack.push(previous);
+        self.notify(SessionEvent::Redone(
+            self.undo_stack.last().unwrap().label.clone(),
+        ));
+        self.undo_stack
+            .last()
+            .map(|revision| revision.label.as_str())
+    }
+
+    /// Returns `true` if there is an edit to undo, and `false` otherwise.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Returns `true` if there is an undone edit to redo, and `false` otherwise.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Returns the label of the edit which would be undone next.
+    pub fn undo_label(&self) -> Option<&str> {
+        self.undo_stack
+            .last()
+            .map(|revision| revision.label.as_str())
+    }
+
+    /// Returns the label of the edit which would be redone next.
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo_stack
+            .last()
+            .map(|revision| revision.label.as_str())
+    }
+
+    /// Returns `true` if the data differs from the last saved state, and `false` otherwise.
+    pub fn is_dirty(&self) -> bool {
+        self.saved_revision != Some(self.revision)
+    }
+
+    /// Marks the current data as saved.
+    pub fn mark_saved(&mut self) {
+        self.saved_revision = Some(self.revision);
+        self.notify(SessionEvent::Saved);
+    }
+
+    /// Writes the current data to the given file path and marks it as saved.
+    pub fn save_to_file<P: AsRef<Path>>(&mut self, path: P) -> BinResult<()> {
+        self.data.write_to_file(path)?;
+        self.mark_saved();
+
+        Ok(())
+    }
+
+    fn swap_revision(&mut self, revision: Revision, label: String) -> Revision {
+        let previous = Revision {
+            label,
+            data: std::mem::replace(&mut self.data, revision.data),
+            revision: self.revision,
+        };
+
+        self.revision = revision.revision;
+
+        previous
+    }
+
+    fn notify(&mut self, event: SessionEvent) {
+        let mut listeners = mem::take(&mut self.listeners);
+
+        for (_, listener) in &mut listeners {
+            listener(&event, self);
+        }
+
+        // Listeners cannot be added while notifying, since they only receive a shared reference.
+        self.listeners = listeners;
+    }
+
+    fn trim_history(&mut self) {
+        if self.undo_stack.len() > self.history_limit {
+            let excess = self.undo_stack.len() - self.history_limit;
+
+            self.undo_stack.drain(..excess);
+        }
+    }
+}
+
+impl fmt::Debug for EffSession {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EffSession")
+            .field("data", &self.data)
+            .field("revision", &self.revision)
+            .field("saved_revision", &self.saved_revision)
+            .field("undo_stack", &self.undo_stack)
+            .field("redo_stack", &self.redo_stack)
+            .field("history_limit", &self.history_limit)
+            .field("listeners", &self.listeners.len())
+            .finish()
+    }
+}
@@ -0,0 +1,93 @@
+use std::{
+    io::{Read, Seek},
+    ops::Deref,
+    path::Path,
+    sync::Arc,
+};
+
+use binrw::BinResult;
+
+use crate::EffData;
+
+/// A cheaply clonable, read-only handle to an [`EffData`], which can be shared between threads.
+///
+/// Cloning the handle does not clone the tables, so every clone refers to the same data.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use std::thread;
+///
+/// use eff_data::{EffData, SharedEffData};
+///
+/// let data = SharedEffData::new(EffData::new().with_resource(vec![0; 4]));
+/// let worker_data = data.clone();
+///
+/// let resource_len = thread::spawn(move || worker_data.resource_data.raw().map(<[u8]>::len))
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(resource_len, Some(4));
+/// assert!(data.ptr_eq(&data.clone()));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedEffData {
+    data: Arc<EffData>,
+}
+
+impl SharedEffData {
+    /// Constructs a new shared handle to the given data.
+    pub fn new(data: EffData) -> Self {
+        Self {
+            data: Arc::new(data),
+        }
+    }
+
+    /// Reads the data from the given file path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> BinResult<Self> {
+        Ok(Self::new(EffData::from_file(path)?))
+    }
+
+    /// Reads the data from the given reader.
+    pub fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        Ok(Self::new(EffData::read(reader)?))
+    }
+
+    /// Returns `true` if both handles refer to the same data, and `false` otherwise.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.data, &other.data)
+    }
+
+    /// Returns the data, cloning it only if other handles to it still exist.
+    pub fn into_data(self) -> EffData {
+        Arc::try_unwrap(self.data).unwrap_or_else(|data| (*data).clone())
+    }
+}
+
+impl Deref for SharedEffData {
+    type Target = EffData;
+
+    fn deref(&self) -> &Self::Target {
+        &self.data
+    }
+}
+
+impl AsRef<EffData> for SharedEffData {
+    fn as_ref(&self) -> &EffData {
+        &self.data
+    }
+}
+
+impl From<EffData> for SharedEffData {
+    fn from(value: EffData) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Arc<EffData>> for SharedEffData {
+    fn from(value: Arc<EffData>) -> Self {
+        Self { data: value }
+    }
+}
@@ -0,0 +1,265 @@
+use std::fmt;
+
+use binrw::BinResult;
+use eff_lib::{PtclFile, PtclTexture, Section};
+
+use crate::{EffData, EmitterData, EmitterSetData, PtclData};
+
+/// The number of entries and sizes of an [`EffData`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct EffStats {
+    /// Number of effect handles.
+    pub effect_handle_count: usize,
+
+    /// Number of effect model entries.
+    pub effect_model_entry_count: usize,
+
+    /// Number of effect group elements across all effect handles.
+    pub effect_group_element_count: usize,
+
+    /// Size in bytes of the name tables, including null terminators.
+    pub name_table_size: usize,
+
+    /// Size in bytes of the contained file resource, or `None` if there is none.
+    #[cfg(feature = "resource")]
+    pub resource_size: Option<usize>,
+}
+
+/// The contents and sizes of a [`PtclData`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PtclStats {
+    /// Number of emitter sets.
+    pub emitter_set_count: usize,
+
+    /// Number of emitters, including child emitters.
+    pub emitter_count: usize,
+
+    /// Number of textures in the texture array.
+    pub texture_count: usize,
+
+    /// Size in bytes of the image data of every texture.
+    pub texture_size: usize,
+
+    /// Name and size in bytes of the binary data of each emitter set, including its emitters and other child sections, in the order the emitter sets are stored in the resource.
+    pub emitter_sets: Vec<(String, usize)>,
+}
+
+/// The effect handles which reference an effect model entry of an [`EffData`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EffectModelUsage {
+    /// Name of the effect model.
+    pub name: String,
+
+    /// Names of the effect handles referencing the effect model, in effect handle order.
+    pub effect_handles: Vec<String>,
+}
+
+impl EffectModelUsage {
+    /// Returns the number of effect handles referencing the effect model.
+    pub fn count(&self) -> usize {
+        self.effect_handles.len()
+    }
+}
+
+impl fmt::Display for EffStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} effect handles, {} effect model entries, {} effect group elements, {} bytes of names",
+            self.effect_handle_count,
+            self.effect_model_entry_count,
+            self.effect_group_element_count,
+            self.name_table_size
+        )?;
+
+        #[cfg(feature = "resource")]
+        if let Some(resource_size) = self.resource_size {
+            write!(f, ", {resource_size} bytes of resource")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl EffData {
+    /// Counts the entries of the data and measures the size of its name tables and resource.
+    ///
+    /// A parsed resource is written to measure its size, which fails if it cannot be written.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectGroupElementData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    /// data.add_effect_handle(EffectHandleData {
+    ///     name: "EFF_TRAIL".to_string(),
+    ///     effect_model_name: "sword".to_string(),
+    ///     effect_group: vec![EffectGroupElementData {
+    ///         parent_joint_name: "haver".to_string(),
+    ///         ..Default::default()
+    ///     }],
+    ///     ..Default::default()
+    /// })
+    /// .unwrap();
+    ///
+    /// let stats = data.stats().unwrap();
+    ///
+    /// assert_eq!(stats.effect_group_element_count, 1);
+    /// assert_eq!(stats.name_table_size, 22);
+    /// assert_eq!(
+    ///     stats.to_string(),
+    ///     "1 effect handles, 1 effect model entries, 1 effect group elements, 22 bytes of names"
+    /// );
+    /// ```
+    pub fn stats(&self) -> BinResult<EffStats> {
+        let name_size = |name: &String| name.len() + 1;
+
+        Ok(EffStats {
+            effect_handle_count: self.effect_handles.len(),
+            effect_model_entry_count: self.effect_model_entries.len(),
+            effect_group_element_count: self
+                .effect_handles
+                .iter()
+                .map(|handle| handle.effect_group.len())
+                .sum(),
+            name_table_size: self
+                .effect_handles
+                .iter()
+                .map(|handle| {
+                    name_size(&handle.name)
+                        + handle
+                            .effect_group
+                            .iter()
+                            .map(|element| name_size(&element.parent_joint_name))
+                            .sum::<usize>()
+                })
+                .chain(
+                    self.effect_model_entries
+                        .iter()
+                        .map(|model| name_size(&model.name)),
+                )
+                .sum(),
+            #[cfg(feature = "resource")]
+            resource_size: self.resource_data.to_bytes()?.map(|bytes| bytes.len()),
+        })
+    }
+
+    /// Lists the effect handles referencing each effect model entry, in effect model entry order.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EffData, EffectHandleData};
+    ///
+    /// let mut data = EffData::new();
+    ///
+    /// for (name, effect_model_name) in [("EFF_SLASH", "sword"), ("EFF_STAB", "sword")] {
+    ///     data.add_effect_handle(EffectHandleData {
+    ///         name: name.to_string(),
+    ///         effect_model_name: effect_model_name.to_string(),
+    ///         ..Default::default()
+    ///     })
+    ///     .unwrap();
+    /// }
+    ///
+    /// let usage = data.model_usage();
+    ///
+    /// assert_eq!(usage[0].name, "sword");
+    /// assert_eq!(usage[0].count(), 2);
+    /// assert_eq!(usage[0].effect_handles, ["EFF_SLASH", "EFF_STAB"]);
+    /// ```
+    pub fn model_usage(&self) -> Vec<EffectModelUsage> {
+        self.effect_model_entries
+            .iter()
+            .map(|model| EffectModelUsage {
+                name: model.name.clone(),
+                effect_handles: self
+                    .handles_for_model(&model.name)
+                    .map(|handle| handle.name.clone())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+impl PtclData {
+    /// Counts the contents of the resource and measures the size of its emitter sets and textures.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_data::{EmitterData, EmitterSetData, PtclData};
+    ///
+    /// let mut emitter = EmitterData::new("puff".to_string());
+    /// emitter.data = vec![0; 0x100];
+    ///
+    /// let mut emitter_set = EmitterSetData::new("smoke".to_string());
+    /// emitter_set.data = vec![0; 0x50];
+    /// emitter_set.emitters.push(emitter);
+    ///
+    /// let mut ptcl = PtclData::default();
+    /// ptcl.emitter_sets.push(emitter_set);
+    ///
+    /// let stats = ptcl.stats().unwrap();
+    ///
+    /// assert_eq!(stats.emitter_count, 1);
+    /// assert_eq!(stats.texture_count, 0);
+    /// assert_eq!(stats.emitter_sets, [("smoke".to_string(), 0x150)]);
+    /// ```
+    pub fn stats(&self) -> BinResult<PtclStats> {
+        let textures = self.textures()?;
+
+        Ok(PtclStats {
+            emitter_set_count: self.emitter_sets.len(),
+            emitter_count: self
+                .emitter_sets
+                .iter()
+                .map(|emitter_set| emitter_set.emitters_depth_first().count())
+                .sum(),
+            texture_count: textures.len(),
+            texture_size: textures.iter().map(|texture| texture.data.len()).sum(),
+            emitter_sets: self
+                .emitter_sets
+                .iter()
+                .map(|emitter_set| (emitter_set.name.clone(), emitter_set_size(emitter_set)))
+                .collect(),
+        })
+    }
+
+    /// Returns the textures stored in the texture array, in the order they are indexed by emitters.
+    pub(crate) fn textures(&self) -> BinResult<Vec<PtclTexture>> {
+        let mut file = PtclFile::new(self.header.clone());
+
+        file.sections.extend(
+            self.sections
+                .iter()
+                .find(|section| section.magic == Section::TEXTURE_ARRAY)
+                .cloned(),
+        );
+
+        file.textures()
+    }
+}
+
+fn emitter_set_size(emitter_set: &EmitterSetData) -> usize {
+    emitter_set.data.len()
+        + emitter_set.emitters.iter().map(emitter_size).sum::<usize>()
+        + emitter_set.children.iter().map(section_size).sum::<usize>()
+}
+
+fn emitter_size(emitter: &EmitterData) -> usize {
+    emitter.data.len()
+        + emitter.emitters.iter().map(emitter_size).sum::<usize>()
+        + emitter.children.iter().map(section_size).sum::<usize>()
+}
+
+fn section_size(section: &Section) -> usize {
+    section.data.len() + section.children.iter().map(section_size).sum::<usize>()
+}
@@ -0,0 +1,78 @@
+use std::io::{self, Write};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::EffData;
+
+/// A single emitter set request in the timeline of an effect handle.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEntry {
+    /// Name of the effect handle.
+    pub effect_handle_name: String,
+
+    /// Frame to request the emitter set on.
+    pub emitter_set_start_frame: i16,
+
+    /// Positive index to the emitter set.
+    pub emitter_set_handle: i16,
+
+    /// Joint name to parent the emitter set to.
+    pub parent_joint_name: String,
+}
+
+impl EffData {
+    /// Returns the effect group elements of every effect handle as a timeline.
+    ///
+    /// Entries are ordered by effect handle, then by start frame.
+    pub fn timeline(&self) -> Vec<TimelineEntry> {
+        self.effect_handles
+            .iter()
+            .flat_map(|handle| {
+                let mut entries = handle
+                    .effect_group
+                    .iter()
+                    .map(|element| TimelineEntry {
+                        effect_handle_name: handle.name.clone(),
+                        emitter_set_start_frame: element.emitter_set_start_frame,
+                        emitter_set_handle: element.emitter_set_handle,
+                        parent_joint_name: element.parent_joint_name.clone(),
+                    })
+                    .collect::<Vec<_>>();
+
+                entries.sort_by_key(|entry| entry.emitter_set_start_frame);
+                entries
+            })
+            .collect()
+    }
+
+    /// Writes the timeline of every effect handle as CSV to the given writer.
+    pub fn write_timeline_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(
+            writer,
+            "effect_handle_name,emitter_set_start_frame,emitter_set_handle,parent_joint_name"
+        )?;
+
+        for entry in self.timeline() {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                csv_field(&entry.effect_handle_name),
+                entry.emitter_set_start_frame,
+                entry.emitter_set_handle,
+                csv_field(&entry.parent_joint_name)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
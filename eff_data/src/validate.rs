@@ -0,0 +1,106 @@
+use std::fmt;
+
+use crate::{EffData, EffectHandleData, EffectModelEntryData};
+
+/// A parent joint name which does not exist in the skeleton it was validated against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownJoint {
+    /// Name of the effect handle containing the effect group element.
+    pub effect_handle_name: String,
+
+    /// Index of the effect group element in the effect group.
+    pub effect_group_index: usize,
+
+    /// Joint name to parent the emitter set to.
+    pub parent_joint_name: String,
+
+    /// Most similar joint name in the skeleton, if one is close enough to be a likely typo.
+    pub suggestion: Option<String>,
+}
+
+impl fmt::Display for UnknownJoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]: unknown joint `{}`",
+            self.effect_handle_name, self.effect_group_index, self.parent_joint_name
+        )?;
+
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, ", did you mean `{suggestion}`?")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl EffData {
+    const MAX_JOINT_SUGGESTION_DISTANCE: usize = 2;
+
+    /// Checks every parent joint name against the given joint names, returning the ones not found.
+    ///
+    /// Empty parent joint names are not parented to a joint, so they are always valid.
+    pub fn validate_parent_joints<S: AsRef<str>>(&self, joint_names: &[S]) -> Vec<UnknownJoint> {
+        let mut unknown_joints = Vec::new();
+
+        for handle in &self.effect_handles {
+            for (i, element) in handle.effect_group.iter().enumerate() {
+                let name = element.parent_joint_name.as_str();
+
+                if name.is_empty() || joint_names.iter().any(|joint| joint.as_ref() == name) {
+                    continue;
+                }
+
+                unknown_joints.push(UnknownJoint {
+                    effect_handle_name: handle.name.clone(),
+                    effect_group_index: i,
+                    parent_joint_name: name.to_string(),
+                    suggestion: joint_names
+                        .iter()
+                        .map(|joint| (joint.as_ref(), edit_distance(joint.as_ref(), name)))
+                        .filter(|(_, distance)| *distance <= Self::MAX_JOINT_SUGGESTION_DISTANCE)
+                        .min_by_key(|(_, distance)| *distance)
+                        .map(|(joint, _)| joint.to_string()),
+                });
+            }
+        }
+
+        unknown_joints
+    }
+
+    /// Returns the effect handles whose effect group elements are not ordered by start frame.
+    ///
+    /// Emitter sets are requested in effect group order, so out-of-order elements can behave unexpectedly.
+    pub fn unsorted_effect_groups(&self) -> impl Iterator<Item = &EffectHandleData> {
+        self.effect_handles
+            .iter()
+            .filter(|handle| !handle.is_effect_group_sorted())
+    }
+
+    /// Returns the effect model entries whose unknown value has not been observed in vanilla files.
+    pub fn unexpected_effect_model_entries(&self) -> impl Iterator<Item = &EffectModelEntryData> {
+        self.effect_model_entries
+            .iter()
+            .filter(|model| !model.unk.is_expected())
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+
+        row[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a_char != *b_char);
+
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
@@ -1,10 +1,13 @@
 use std::{
     fs,
+    io::{self, Write},
+    mem,
     path::{Path, PathBuf},
 };
 
 use clap::Parser;
-use eff_data::EffData;
+use eff_data::{ConstantsLanguage, EffData, EffMeta, FlagNames};
+use serde_json::Value;
 
 /// Convert EFF files to and from JSON
 #[derive(Parser)]
@@ -13,17 +16,32 @@ struct Args {
     /// The input EFF or JSON file path
     pub input: String,
 
-    /// The output EFF or JSON file path
+    /// The output EFF or JSON file path, or `-` to write to standard output
     pub output: Option<String>,
 
     /// The input or output PTCL file path
     pub ptcl: Option<String>,
+
+    /// The output CSV or JSON file path for the effect timeline
+    #[arg(long)]
+    pub timeline: Option<String>,
+
+    /// The output Rust or C file path for the effect handle constants
+    #[arg(long)]
+    pub constants: Option<String>,
+
+    /// The input JSON or TOML file path for custom flag names
+    #[arg(long)]
+    pub flag_names: Option<String>,
 }
 
 fn read_data_write_json<P: AsRef<Path> + ToString>(
     input_path: P,
     output_path: Option<String>,
     ptcl_path: Option<String>,
+    timeline_path: Option<String>,
+    constants_path: Option<String>,
+    flag_names: Option<FlagNames>,
 ) {
     let output_path = output_path
         .map(PathBuf::from)
@@ -32,13 +50,42 @@ fn read_data_write_json<P: AsRef<Path> + ToString>(
         .map(PathBuf::from)
         .unwrap_or_else(|| input_path.as_ref().with_extension("ptcl"));
 
+    let meta = EffMeta::read_sidecar(input_path.as_ref()).expect("failed to read metadata file");
+
     match EffData::from_file(input_path) {
-        Ok(eff) => {
-            let json = serde_json::to_string_pretty(&eff).unwrap();
+        Ok(mut eff) => {
+            let flag_names = match meta {
+                Some(meta) => {
+                    eff.apply_meta(&meta);
+                    flag_names.or((!meta.flag_names.is_empty()).then_some(meta.flag_names))
+                }
+                None => flag_names,
+            };
+            let mut json = serde_json::to_value(&eff).unwrap();
+
+            if let Some(flag_names) = flag_names {
+                rename_flags(&mut json, |name| flag_names.name(name).to_string());
+            }
 
-            fs::write(output_path, json).expect("failed to write JSON file");
+            let json = serde_json::to_string_pretty(&json).unwrap();
+
+            if is_stdout(&output_path) {
+                io::stdout()
+                    .write_all(json.as_bytes())
+                    .expect("failed to write JSON to standard output");
+            } else {
+                fs::write(output_path, json).expect("failed to write JSON file");
+            }
             eff.write_resource_to_file(ptcl_path)
                 .expect("failed to write PTCL file");
+
+            if let Some(timeline_path) = timeline_path {
+                write_timeline(&eff, timeline_path);
+            }
+
+            if let Some(constants_path) = constants_path {
+                write_constants(&eff, constants_path);
+            }
         }
         Err(error) => eprintln!("{error:?}"),
     }
@@ -48,10 +95,19 @@ fn read_json_write_data<P: AsRef<Path>>(
     input_path: P,
     output_path: Option<String>,
     ptcl_path: Option<String>,
+    flag_names: Option<FlagNames>,
 ) {
     let json = fs::read_to_string(&input_path).unwrap();
 
-    match serde_json::from_str::<EffData>(&json) {
+    match serde_json::from_str::<Value>(&json).and_then(|mut json| {
+        if let Some(flag_names) = &flag_names {
+            rename_flags(&mut json, |name| {
+                flag_names.field_name(name).unwrap_or(name).to_string()
+            });
+        }
+
+        serde_json::from_value::<EffData>(json)
+    }) {
         Ok(mut eff) => {
             let output_path = output_path
                 .map(PathBuf::from)
@@ -63,16 +119,102 @@ fn read_json_write_data<P: AsRef<Path>>(
                     .with_extension("ptcl")
             });
 
-            eff.resource_data = fs::read(ptcl_path).ok();
-            eff.write_to_file(output_path)
-                .expect("failed to write EFF file");
+            eff.resource_data = fs::read(ptcl_path).ok().into();
+
+            if is_stdout(&output_path) {
+                eff.write_unseekable(&mut io::stdout().lock())
+                    .expect("failed to write EFF to standard output");
+            } else {
+                eff.write_to_file(&output_path)
+                    .expect("failed to write EFF file");
+                write_meta(&eff, flag_names, output_path);
+            }
         }
         Err(error) => eprintln!("{error:?}"),
     }
 }
 
+fn is_stdout(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+fn read_flag_names<P: AsRef<Path>>(flag_names_path: P) -> FlagNames {
+    let text = fs::read_to_string(&flag_names_path).expect("failed to read flag names file");
+
+    match flag_names_path
+        .as_ref()
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("toml") => toml::from_str(&text).expect("failed to parse flag names file"),
+        _ => serde_json::from_str(&text).expect("failed to parse flag names file"),
+    }
+}
+
+fn write_meta<P: AsRef<Path>>(eff: &EffData, flag_names: Option<FlagNames>, eff_path: P) {
+    let mut meta = EffMeta::from_data(eff);
+
+    meta.flag_names = flag_names.unwrap_or_default();
+
+    if !meta.is_empty() || EffMeta::sidecar_path(&eff_path).exists() {
+        meta.write_sidecar(eff_path)
+            .expect("failed to write metadata file");
+    }
+}
+
+fn rename_flags<F: Fn(&str) -> String>(json: &mut Value, rename: F) {
+    let Some(handles) = json.get_mut("effect_handles").and_then(Value::as_array_mut) else {
+        return;
+    };
+
+    for handle in handles {
+        if let Some(Value::Object(flags)) = handle.get_mut("flags") {
+            *flags = mem::take(flags)
+                .into_iter()
+                .map(|(name, value)| (rename(&name), value))
+                .collect();
+        }
+    }
+}
+
+fn write_timeline<P: AsRef<Path>>(eff: &EffData, timeline_path: P) {
+    match timeline_path
+        .as_ref()
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("json") => {
+            let json = serde_json::to_string_pretty(&eff.timeline()).unwrap();
+
+            fs::write(timeline_path, json).expect("failed to write timeline file");
+        }
+        _ => {
+            let mut csv = Vec::new();
+
+            eff.write_timeline_csv(&mut csv).unwrap();
+            fs::write(timeline_path, csv).expect("failed to write timeline file");
+        }
+    }
+}
+
+fn write_constants<P: AsRef<Path>>(eff: &EffData, constants_path: P) {
+    let language = match constants_path
+        .as_ref()
+        .extension()
+        .and_then(|extension| extension.to_str())
+    {
+        Some("h" | "hpp" | "c" | "cpp") => ConstantsLanguage::C,
+        _ => ConstantsLanguage::Rust,
+    };
+    let mut source = Vec::new();
+
+    eff.write_constants(&mut source, language).unwrap();
+    fs::write(constants_path, source).expect("failed to write constants file");
+}
+
 fn main() {
     let args = Args::parse();
+    let flag_names = args.flag_names.map(read_flag_names);
 
     match Path::new(&args.input)
         .extension()
@@ -80,7 +222,14 @@ fn main() {
         .to_str()
         .unwrap()
     {
-        "json" => read_json_write_data(args.input, args.output, args.ptcl),
-        _ => read_data_write_json(args.input, args.output, args.ptcl),
+        "json" => read_json_write_data(args.input, args.output, args.ptcl, flag_names),
+        _ => read_data_write_json(
+            args.input,
+            args.output,
+            args.ptcl,
+            args.timeline,
+            args.constants,
+            flag_names,
+        ),
     }
 }
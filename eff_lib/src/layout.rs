@@ -0,0 +1,145 @@
+use std::mem;
+
+use crate::{CString, EffFile, EffectGroupElement, EffectHandle, EffectModelEntry};
+
+/// The position and size of a region of an EFF file, in bytes.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub struct Region {
+    /// Offset of the region from the start of the file.
+    pub offset: u64,
+
+    /// Size of the region.
+    pub size: u64,
+}
+
+/// The regions of an EFF file, as it would be written.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_lib::{EffFile, Region};
+///
+/// let eff = EffFile::read(&mut std::io::Cursor::new(b"EFFN\0\0\x02\0\0\0\0\0\0\0\xFF\xFF")).unwrap();
+/// let layout = eff.layout();
+///
+/// assert_eq!(layout.effect_handles, Region { offset: 0x10, size: 0 });
+/// assert_eq!(layout.resource, None);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub struct EffLayout {
+    /// Region of the header.
+    pub header: Region,
+
+    /// Region of the effect handle table.
+    pub effect_handles: Region,
+
+    /// Region of the effect group element table.
+    pub effect_group_elements: Region,
+
+    /// Region of the effect model entry table.
+    pub effect_model_entries: Region,
+
+    /// Region of the effect handle name table.
+    pub effect_handle_names: Region,
+
+    /// Region of the effect model name table.
+    pub effect_model_names: Region,
+
+    /// Region of the parent joint name table.
+    pub parent_joint_names: Region,
+
+    /// Region of the resource data, or `None` if there is no resource data.
+    pub resource: Option<Region>,
+}
+
+impl Region {
+    /// Returns the offset of the end of the region.
+    pub fn end(&self) -> u64 {
+        self.offset + self.size
+    }
+
+    fn after(previous: Region, size: usize) -> Self {
+        Self {
+            offset: previous.end(),
+            size: size as u64,
+        }
+    }
+}
+
+impl EffLayout {
+    /// Returns every region in file order, along with its name.
+    pub fn regions(&self) -> impl Iterator<Item = (&'static str, Region)> {
+        [
+            ("header", Some(self.header)),
+            ("effect_handles", Some(self.effect_handles)),
+            ("effect_group_elements", Some(self.effect_group_elements)),
+            ("effect_model_entries", Some(self.effect_model_entries)),
+            ("effect_handle_names", Some(self.effect_handle_names)),
+            ("effect_model_names", Some(self.effect_model_names)),
+            ("parent_joint_names", Some(self.parent_joint_names)),
+            ("resource", self.resource),
+        ]
+        .into_iter()
+        .filter_map(|(name, region)| Some((name, region?)))
+    }
+}
+
+impl EffFile {
+    /// Returns the offset and size of every table and the resource data, as they would be written.
+    ///
+    /// Files read from elsewhere are laid out the same way, unless they were written with a different resource alignment.
+    pub fn layout(&self) -> EffLayout {
+        let header = Region {
+            offset: 0,
+            size: 0x10,
+        };
+        let effect_handles = Region::after(
+            header,
+            self.effect_handles.len() * mem::size_of::<EffectHandle>(),
+        );
+        let effect_group_elements = Region::after(
+            effect_handles,
+            self.effect_group_elements.len() * mem::size_of::<EffectGroupElement>(),
+        );
+        let effect_model_entries = Region::after(
+            effect_group_elements,
+            self.effect_model_entries.len() * mem::size_of::<EffectModelEntry>(),
+        );
+        let effect_handle_names =
+            Region::after(effect_model_entries, names_size(&self.effect_handle_names));
+        let effect_model_names =
+            Region::after(effect_handle_names, names_size(&self.effect_model_names));
+        let parent_joint_names =
+            Region::after(effect_model_names, names_size(&self.parent_joint_names));
+
+        EffLayout {
+            header,
+            effect_handles,
+            effect_group_elements,
+            effect_model_entries,
+            effect_handle_names,
+            effect_model_names,
+            parent_joint_names,
+            resource: resource_region(self),
+        }
+    }
+}
+
+#[cfg(feature = "resource")]
+fn resource_region(eff: &EffFile) -> Option<Region> {
+    Some(Region {
+        offset: eff.resource_offset()?,
+        size: eff.resource_data.as_ref()?.len() as u64,
+    })
+}
+
+#[cfg(not(feature = "resource"))]
+fn resource_region(_eff: &EffFile) -> Option<Region> {
+    None
+}
+
+fn names_size(names: &[CString]) -> usize {
+    names.iter().map(|name| name.len() + 1).sum()
+}
@@ -1,24 +1,57 @@
 //! # eff_lib
 //!
 //! eff_lib is a library for reading and writing EFF files from Super Smash Bros. Ultimate.
+//!
+//! Without the default `std` feature, only the borrowed [`EffView`] is available, and the crate is `no_std`.
+//!
+//! Without the default `resource` feature, the file resource following the tables is skipped, for tools which only need the tables.
+//!
+//! With the `dds` feature, textures of a [`PtclFile`] can be converted to DDS files.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(all(feature = "std", feature = "resource"))]
+use std::io;
+#[cfg(feature = "std")]
 use std::{
-    fs,
-    io::{self, Cursor, Read, Seek, Write},
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io::{Cursor, Read, Seek, Write},
     mem,
     path::Path,
 };
 
+#[cfg(feature = "std")]
 use binrw::{binrw, BinReaderExt, BinResult, BinWrite};
+#[cfg(feature = "std")]
 use modular_bitfield::prelude::*;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "std")]
+mod layout;
+#[cfg(feature = "std")]
+mod ptcl;
+#[cfg(feature = "std")]
 mod string;
-
+mod view;
+
+#[cfg(feature = "std")]
+pub use layout::{EffLayout, Region};
+#[cfg(feature = "dds")]
+pub use ptcl::DdsError;
+#[cfg(feature = "std")]
+pub use ptcl::{
+    AttributeKind, EmitterField, EmitterSetView, EmitterView, GravityField, MagnetField, ObjError,
+    PtclFile, PtclHeader, PtclPrimitive, PtclShader, PtclTexture, Section, TurbulenceField,
+    UnknownSection, VertexAttribute,
+};
+#[cfg(feature = "std")]
 pub use string::CString;
+pub use view::{EffView, EffectGroupElementView, EffectHandleView, NameTable, ViewError};
 
 /// The container type for the EFF file format.
+#[cfg(feature = "std")]
 #[binrw]
 #[brw(magic = b"EFFN")]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -69,12 +102,15 @@ pub struct EffFile {
     pub parent_joint_names: Vec<CString>,
 
     /// Data buffer for the contained file resource.
+    ///
+    /// Without the `resource` feature, the resource is neither read nor written, so this is always `None` when read.
     #[br(parse_with = binrw::helpers::until_eof)]
-    #[brw(if(resource_alignment_factor != -1), align_before = Self::calculate_resource_alignment(resource_alignment_factor))]
+    #[brw(if(cfg!(feature = "resource") && resource_alignment_factor != -1), align_before = Self::calculate_resource_alignment(resource_alignment_factor))]
     #[cfg_attr(feature = "serde", serde(skip))]
     pub resource_data: Option<Vec<u8>>,
 }
 
+#[cfg(feature = "std")]
 impl EffFile {
     const RESOURCE_ALIGNMENT_COEFFICIENT: usize = 0x1000;
 
@@ -93,11 +129,34 @@ impl EffFile {
         Ok(eff)
     }
 
+    /// Reads the data from the given reader, which does not need to support seeking.
+    ///
+    /// The reader is read to the end into an internal buffer first, so this is suitable for decompression streams and sockets.
+    pub fn read_unseekable<R: Read>(reader: &mut R) -> BinResult<Self> {
+        let mut buffer = Vec::new();
+
+        reader.read_to_end(&mut buffer)?;
+
+        Self::read(&mut Cursor::new(buffer))
+    }
+
     /// Writes the data to the given writer.
     pub fn write<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
         self.write_le(writer)
     }
 
+    /// Writes the data to the given writer, which does not need to support seeking.
+    ///
+    /// The data is written to an internal buffer first, so this is suitable for pipes and network streams.
+    pub fn write_unseekable<W: Write>(&self, writer: &mut W) -> BinResult<()> {
+        let mut cursor = Cursor::new(Vec::new());
+
+        self.write_le(&mut cursor)?;
+        writer.write_all(cursor.get_ref())?;
+
+        Ok(())
+    }
+
     /// Writes the data to the given file path.
     pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
         let mut cursor = Cursor::new(Vec::new());
@@ -109,6 +168,7 @@ impl EffFile {
     }
 
     /// Writes the data from the resource data buffer to the given file path.
+    #[cfg(feature = "resource")]
     pub fn write_resource_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
         if let Some(resource_data) = &self.resource_data {
             fs::write(path, resource_data)?;
@@ -117,8 +177,132 @@ impl EffFile {
         Ok(())
     }
 
+    /// Parses the resource data buffer as a PTCL resource, if there is one.
+    #[cfg(feature = "resource")]
+    pub fn ptcl(&self) -> Option<BinResult<PtclFile>> {
+        self.resource_data.as_deref().map(PtclFile::from_bytes)
+    }
+
+    /// Returns the offset the resource data is written at, or `None` if there is no resource data.
+    ///
+    /// The offset only depends on the tables, so it does not change when the resource data is replaced.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::EffFile;
+    ///
+    /// let mut eff = EffFile::read(&mut std::io::Cursor::new(b"EFFN\0\0\x02\0\0\0\0\0\0\0\xFF\xFF")).unwrap();
+    ///
+    /// assert_eq!(eff.resource_offset(), None);
+    ///
+    /// eff.resource_data = Some(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(eff.resource_offset(), Some(0x1000));
+    /// ```
+    #[cfg(feature = "resource")]
+    pub fn resource_offset(&self) -> Option<u64> {
+        self.resource_data.as_ref()?;
+
+        Some(
+            Self::calculate_resource_alignment(self.calculate_resource_alignment_factor() as i16)
+                as u64,
+        )
+    }
+
+    /// Returns the EFF file with the given resource data.
+    ///
+    /// The resource data is aligned when written, as described by [`resource_offset`](Self::resource_offset).
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::EffFile;
+    ///
+    /// let eff = EffFile::read(&mut std::io::Cursor::new(b"EFFN\0\0\x02\0\0\0\0\0\0\0\xFF\xFF")).unwrap();
+    /// let mut eff = eff.with_resource(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(eff.set_resource(vec![4, 5]), Some(vec![1, 2, 3]));
+    /// assert_eq!(eff.take_resource(), Some(vec![4, 5]));
+    /// assert_eq!(eff.resource_data, None);
+    /// ```
+    #[cfg(feature = "resource")]
+    pub fn with_resource(mut self, resource_data: Vec<u8>) -> Self {
+        self.resource_data = Some(resource_data);
+        self
+    }
+
+    /// Replaces the resource data, returning the previous resource data if there was any.
+    #[cfg(feature = "resource")]
+    pub fn set_resource(&mut self, resource_data: Vec<u8>) -> Option<Vec<u8>> {
+        self.resource_data.replace(resource_data)
+    }
+
+    /// Removes the resource data, returning it if there was any.
+    #[cfg(feature = "resource")]
+    pub fn take_resource(&mut self) -> Option<Vec<u8>> {
+        self.resource_data.take()
+    }
+
+    /// Replaces the resource data of the EFF file at the given path, rewriting only the resource region instead of the whole file.
+    ///
+    /// The tables are left untouched, which is valid because the offset of the resource data only depends on them.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or written, is not an EFF file, or has no resource data to replace.
+    #[cfg(feature = "resource")]
+    pub fn swap_resource_in_file<P: AsRef<Path>>(path: P, resource_data: &[u8]) -> BinResult<()> {
+        let mut file = fs::OpenOptions::new().read(true).write(true).open(path)?;
+        let mut header = [0; 0x10];
+
+        file.read_exact(&mut header)?;
+
+        if &header[..4] != b"EFFN" {
+            return Err(binrw::Error::BadMagic {
+                pos: 0,
+                found: Box::new(header[..4].to_vec()),
+            });
+        }
+
+        let factor = i16::from_le_bytes([header[0xE], header[0xF]]);
+        let offset = match factor {
+            -1 => {
+                return Err(binrw::Error::AssertFail {
+                    pos: 0xE,
+                    message: "the EFF file has no resource data to replace".to_string(),
+                })
+            }
+            1.. => Self::calculate_resource_alignment(factor) as u64,
+            // Resources without an alignment follow the tables directly, so the tables must be read to find them.
+            _ => {
+                let mut data = Vec::new();
+
+                file.rewind()?;
+                file.read_to_end(&mut data)?;
+
+                let view = EffView::parse(&data).map_err(|error| binrw::Error::Custom {
+                    pos: 0,
+                    err: Box::new(error),
+                })?;
+
+                (data.len() - view.resource_data().map_or(0, <[u8]>::len)) as u64
+            }
+        };
+
+        file.seek(io::SeekFrom::Start(offset))?;
+        file.write_all(resource_data)?;
+        file.set_len(offset + resource_data.len() as u64)?;
+
+        Ok(())
+    }
+
     fn calculate_resource_alignment_factor(&self) -> usize {
-        if self.resource_data.is_none() {
+        if !cfg!(feature = "resource") || self.resource_data.is_none() {
             return usize::MAX;
         }
 
@@ -154,6 +338,7 @@ impl EffFile {
 }
 
 /// The data associated with an effect handle.
+#[cfg(feature = "std")]
 #[binrw]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
@@ -175,6 +360,7 @@ pub struct EffectHandle {
 }
 
 /// Flags for an [`EffectHandle`] representing the attributes of an effect.
+#[cfg(feature = "std")]
 #[bitfield(bits = 32)]
 #[binrw]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -219,7 +405,56 @@ pub struct EffectHandleFlags {
     pub unk_32: bool,
 }
 
+#[cfg(feature = "std")]
+impl fmt::Display for EffectHandleFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = [
+            ("unk_01", self.unk_01()),
+            ("unk_02", self.unk_02()),
+            ("unk_03", self.unk_03()),
+            ("unk_04", self.unk_04()),
+            ("unk_05", self.unk_05()),
+            ("unk_06", self.unk_06()),
+            ("unk_07", self.unk_07()),
+            ("unk_09", self.unk_09()),
+            ("unk_10", self.unk_10()),
+            ("unk_13", self.unk_13()),
+            ("unk_14", self.unk_14()),
+            ("unk_15", self.unk_15()),
+            ("unk_16", self.unk_16()),
+            ("unk_17", self.unk_17()),
+            ("hit_effect", self.hit_effect()),
+            ("unk_20", self.unk_20()),
+            ("unk_21", self.unk_21()),
+            ("unk_23", self.unk_23()),
+            ("update_always", self.update_always()),
+            ("unk_25", self.unk_25()),
+            ("unk_26", self.unk_26()),
+            ("unk_29", self.unk_29()),
+            ("unk_30", self.unk_30()),
+            ("unk_31", self.unk_31()),
+            ("unk_32", self.unk_32()),
+        ];
+        let mut set_flags = flags
+            .iter()
+            .filter(|(_, value)| *value)
+            .map(|(name, _)| name);
+
+        match set_flags.next() {
+            Some(name) => write!(f, "{name}")?,
+            None => return write!(f, "none"),
+        }
+
+        for name in set_flags {
+            write!(f, ", {name}")?;
+        }
+
+        Ok(())
+    }
+}
+
 /// The data associated with an effect group element.
+#[cfg(feature = "std")]
 #[binrw]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
@@ -232,10 +467,92 @@ pub struct EffectGroupElement {
 }
 
 /// The data associated with an effect model entry.
+#[cfg(feature = "std")]
 #[binrw]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq)]
 pub struct EffectModelEntry {
     // TODO: Determine the purpose of this field. Only equal to zero or one, and only checked if zero.
-    pub unk: i8,
+    #[br(map = i8::into)]
+    #[bw(map = |unk: &EffectModelEntryUnk| i8::from(*unk))]
+    pub unk: EffectModelEntryUnk,
+}
+
+/// The value of [`EffectModelEntry::unk`], whose purpose is not yet known.
+///
+/// Values other than zero or one have not been observed in vanilla files, but are preserved as-is.
+/// Values are compared by the byte they are written as, so `Other(0)` and `Other(1)` are equal to [`Zero`](Self::Zero) and [`One`](Self::One).
+/// Converting from an [`i8`] always constructs values in this normalized form.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_lib::EffectModelEntryUnk;
+///
+/// assert_eq!(EffectModelEntryUnk::from(1), EffectModelEntryUnk::One);
+/// assert_eq!(EffectModelEntryUnk::Other(1), EffectModelEntryUnk::One);
+/// assert_eq!(i8::from(EffectModelEntryUnk::Other(5)), 5);
+/// assert!(!EffectModelEntryUnk::from(5).is_expected());
+/// assert!(EffectModelEntryUnk::Other(0).is_expected());
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "i8", into = "i8"))]
+#[derive(Debug, Clone, Copy, Default)]
+pub enum EffectModelEntryUnk {
+    #[default]
+    Zero,
+    One,
+    Other(i8),
+}
+
+#[cfg(feature = "std")]
+impl EffectModelEntryUnk {
+    /// Returns `true` if the value has been observed in vanilla files, and `false` otherwise.
+    ///
+    /// This is the only validation of the value until its meaning is known, and is meant for tools which warn about unusual data.
+    pub fn is_expected(self) -> bool {
+        matches!(i8::from(self), 0 | 1)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for EffectModelEntryUnk {
+    fn eq(&self, other: &Self) -> bool {
+        i8::from(*self) == i8::from(*other)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for EffectModelEntryUnk {}
+
+#[cfg(feature = "std")]
+impl Hash for EffectModelEntryUnk {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        i8::from(*self).hash(state);
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<i8> for EffectModelEntryUnk {
+    fn from(value: i8) -> Self {
+        match value {
+            0 => Self::Zero,
+            1 => Self::One,
+            _ => Self::Other(value),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<EffectModelEntryUnk> for i8 {
+    fn from(value: EffectModelEntryUnk) -> Self {
+        match value {
+            EffectModelEntryUnk::Zero => 0,
+            EffectModelEntryUnk::One => 1,
+            EffectModelEntryUnk::Other(value) => value,
+        }
+    }
 }
@@ -0,0 +1,467 @@
+use std::{
+    borrow::Cow,
+    fs,
+    io::{Cursor, Read, Seek, SeekFrom, Write},
+    path::Path,
+    str,
+};
+
+use binrw::{binrw, BinReaderExt, BinResult, BinWrite, Endian};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "dds")]
+mod dds;
+mod field;
+mod obj;
+mod primitive;
+mod section;
+mod shader;
+mod texture;
+mod unknown;
+
+#[cfg(feature = "dds")]
+pub use dds::DdsError;
+pub use field::{EmitterField, GravityField, MagnetField, TurbulenceField};
+pub use obj::ObjError;
+pub use primitive::{AttributeKind, PtclPrimitive, VertexAttribute};
+pub use section::Section;
+pub use shader::PtclShader;
+pub use texture::PtclTexture;
+pub use unknown::UnknownSection;
+
+/// The container type for the PTCL effect resource embedded in an EFF file.
+///
+/// The resource is a tree of [`Section`]s. Emitter sets and their emitters can be inspected through [`PtclFile::emitter_sets`], and every other section is kept as-is.
+///
+/// With the `serde` feature, the offsets the sections were read at are not kept, so a deserialized resource is laid out anew when written.
+///
+/// Both little-endian resources from Super Smash Bros. Ultimate and big-endian resources from Super Smash Bros. for Wii U can be read.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_lib::{PtclFile, PtclHeader, Section};
+///
+/// let mut emitter_set_data = vec![0; 0x50];
+/// emitter_set_data[0x10..0x15].copy_from_slice(b"smoke");
+///
+/// let mut emitter_set = Section::new(Section::EMITTER_SET, emitter_set_data);
+/// emitter_set.children.push(Section::new(Section::EMITTER, vec![0; 0x50]));
+///
+/// let mut emitter_set_array = Section::new(Section::EMITTER_SET_ARRAY, Vec::new());
+/// emitter_set_array.children.push(emitter_set);
+///
+/// let mut ptcl = PtclFile::new(PtclHeader::default());
+/// ptcl.sections.push(emitter_set_array);
+///
+/// let ptcl = PtclFile::from_bytes(&ptcl.to_bytes().unwrap()).unwrap();
+/// let emitter_set = ptcl.emitter_sets().next().unwrap();
+///
+/// assert_eq!(emitter_set.name(), Ok("smoke"));
+/// assert_eq!(emitter_set.emitters().count(), 1);
+/// assert_eq!(ptcl.emitter_set_names(), ["smoke"]);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtclFile {
+    /// Header of the resource.
+    pub header: PtclHeader,
+
+    /// Collection of top-level sections.
+    pub sections: Vec<Section>,
+
+    /// Byte order of the header and section headers, detected from the byte order mark when read.
+    ///
+    /// Changing the byte order only swaps the headers, as the layout of the binary data of each section is not known in general.
+    #[cfg_attr(feature = "serde", serde(with = "endian"))]
+    pub endian: Endian,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    file_size: Option<u32>,
+}
+
+/// The header of a [`PtclFile`].
+#[binrw]
+#[brw(magic = b"VFXB")]
+#[bw(import(file_size: u32))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtclHeader {
+    // TODO: Determine the purpose of this field.
+    pub unk: [u8; 4],
+
+    /// Version of the graphics API the resource was built for.
+    pub graphics_api_version: u16,
+
+    /// Version of the effect runtime the resource was built for.
+    pub vfx_version: u16,
+
+    #[br(temp, assert(byte_order_mark == 0xFEFF, "unsupported byte order mark {:#06X}", byte_order_mark))]
+    #[bw(calc = 0xFEFF)]
+    byte_order_mark: u16,
+
+    /// Base-2 logarithm of the alignment of the resource.
+    pub alignment: u8,
+
+    /// Size of addresses on the target platform, in bytes.
+    pub target_offset: u8,
+
+    /// Size of the header, in bytes.
+    pub header_size: u32,
+
+    /// Flags of the resource.
+    pub flag: u16,
+
+    /// Offset to the first section.
+    pub block_offset: u16,
+
+    #[br(temp)]
+    #[bw(calc = 0)]
+    _padding: u32,
+
+    #[br(temp)]
+    #[bw(calc = file_size)]
+    _file_size: u32,
+}
+
+impl Default for PtclHeader {
+    fn default() -> Self {
+        Self {
+            unk: *b"    ",
+            graphics_api_version: 0,
+            vfx_version: 0,
+            alignment: 0xC,
+            target_offset: 0x40,
+            header_size: PtclFile::HEADER_SIZE as u32,
+            flag: 0,
+            block_offset: PtclFile::HEADER_SIZE as u16,
+        }
+    }
+}
+
+impl PtclFile {
+    const HEADER_SIZE: u64 = 0x20;
+    const BYTE_ORDER_MARK_OFFSET: usize = 0xC;
+    const EMITTER_SET_NAME_OFFSET: usize = 0x10;
+    const EMITTER_NAME_OFFSET: usize = 0x10;
+    const NAME_LENGTH: usize = 0x40;
+
+    /// Constructs a new little-endian resource with the given header and no sections.
+    pub fn new(header: PtclHeader) -> Self {
+        Self {
+            header,
+            sections: Vec::new(),
+            endian: Endian::Little,
+            file_size: None,
+        }
+    }
+
+    /// Reads the data from the given file path.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> BinResult<Self> {
+        Self::read(&mut Cursor::new(fs::read(path)?))
+    }
+
+    /// Reads the data from the given byte slice, such as the resource data of an EFF file.
+    pub fn from_bytes(bytes: &[u8]) -> BinResult<Self> {
+        Self::read(&mut Cursor::new(bytes))
+    }
+
+    /// Reads the data from the given reader.
+    ///
+    /// Section offsets are relative to the start of the resource, so the rest of the reader is buffered first.
+    /// The byte order is detected from the byte order mark in the header.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the header is invalid or a section or its data lies outside of the resource.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    ///
+    /// use eff_lib::{PtclFile, PtclHeader, Section};
+    ///
+    /// let mut ptcl = PtclFile::new(PtclHeader::default());
+    /// ptcl.sections
+    ///     .push(Section::new(Section::EMITTER_SET_ARRAY, vec![0; 4]));
+    ///
+    /// let mut bytes = ptcl.to_bytes().unwrap();
+    ///
+    /// let read = PtclFile::read(&mut Cursor::new(&bytes)).unwrap();
+    ///
+    /// assert_eq!(read.sections[0].data, [0; 4]);
+    ///
+    /// // Claim a section size far larger than the resource.
+    /// let size_offset = ptcl.header.block_offset as usize + 4;
+    /// bytes[size_offset..size_offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    ///
+    /// assert!(PtclFile::read(&mut Cursor::new(&bytes)).is_err());
+    /// ```
+    pub fn read<R: Read + Seek>(reader: &mut R) -> BinResult<Self> {
+        let mut buffer = Vec::new();
+
+        reader.read_to_end(&mut buffer)?;
+
+        let endian =
+            match buffer.get(Self::BYTE_ORDER_MARK_OFFSET..Self::BYTE_ORDER_MARK_OFFSET + 2) {
+                Some([0xFE, 0xFF]) => Endian::Big,
+                _ => Endian::Little,
+            };
+        let mut reader = Cursor::new(buffer);
+        let header = reader.read_type::<PtclHeader>(endian)?;
+
+        reader.seek(SeekFrom::Start(Self::HEADER_SIZE - 4))?;
+
+        let file_size = reader.read_type::<u32>(endian)?;
+        // Resources without sections end right after the header.
+        let sections = if header.block_offset as usize >= reader.get_ref().len() {
+            Vec::new()
+        } else {
+            Section::read_chain(&mut reader, header.block_offset as u64, endian)?
+        };
+
+        Ok(Self {
+            header,
+            sections,
+            endian,
+            file_size: Some(file_size),
+        })
+    }
+
+    /// Writes the data to the given writer.
+    pub fn write<W: Write + Seek>(&self, writer: &mut W) -> BinResult<()> {
+        writer.write_all(&self.to_bytes()?)?;
+
+        Ok(())
+    }
+
+    /// Writes the data to the given file path.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> BinResult<()> {
+        fs::write(path, self.to_bytes()?)?;
+
+        Ok(())
+    }
+
+    /// Writes the data to a new buffer.
+    ///
+    /// Sections which have not moved since they were read keep their original offsets, and every other section is laid out after them.
+    /// The written sections are read back to verify that the binary data of each section is aligned.
+    pub fn to_bytes(&self) -> BinResult<Vec<u8>> {
+        let block_offset = (self.header.block_offset as u64).max(Self::HEADER_SIZE);
+        let mut buffer = vec![0; block_offset as usize];
+        let end = Section::write_chain(&self.sections, &mut buffer, block_offset, self.endian);
+        let file_size = self
+            .file_size
+            .filter(|file_size| *file_size as u64 >= end)
+            .unwrap_or(end as u32);
+
+        buffer.resize(file_size as usize, 0);
+
+        let mut header = Cursor::new(Vec::new());
+
+        self.header
+            .write_options(&mut header, self.endian, (file_size,))?;
+        buffer[..Self::HEADER_SIZE as usize].copy_from_slice(header.get_ref());
+
+        if !self.sections.is_empty() {
+            let written =
+                Section::read_chain(&mut Cursor::new(&buffer), block_offset, self.endian)?;
+
+            Section::verify_chain(&self.sections, &written)?;
+        }
+
+        Ok(buffer)
+    }
+
+    /// Discards the offsets the sections were read at, so every section is laid out anew with its alignment when written.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::{PtclFile, PtclHeader, Section};
+    ///
+    /// let mut ptcl = PtclFile::new(PtclHeader::default());
+    /// ptcl.sections.push(Section::new(Section::TEXTURE_ARRAY, vec![0; 0x10]));
+    ///
+    /// let mut ptcl = PtclFile::from_bytes(&ptcl.to_bytes().unwrap()).unwrap();
+    /// ptcl.sections[0].alignment = 0x1000;
+    /// ptcl.rebuild();
+    ///
+    /// let bytes = ptcl.to_bytes().unwrap();
+    ///
+    /// assert_eq!(bytes.len(), 0x1010);
+    /// ```
+    pub fn rebuild(&mut self) {
+        self.file_size = None;
+
+        for section in &mut self.sections {
+            section.clear_layout();
+        }
+    }
+
+    /// Returns the first top-level section with the given identifier.
+    pub fn section(&self, magic: &[u8; 4]) -> Option<&Section> {
+        self.sections.iter().find(|section| &section.magic == magic)
+    }
+
+    /// Returns the first top-level section with the given identifier.
+    pub fn section_mut(&mut self, magic: &[u8; 4]) -> Option<&mut Section> {
+        self.sections
+            .iter_mut()
+            .find(|section| &section.magic == magic)
+    }
+
+    /// Returns an iterator over the emitter sets, in the order they are indexed by emitter set handles.
+    pub fn emitter_sets(&self) -> impl Iterator<Item = EmitterSetView<'_>> {
+        self.section(&Section::EMITTER_SET_ARRAY)
+            .into_iter()
+            .flat_map(|array| array.children_of(&Section::EMITTER_SET))
+            .map(move |section| EmitterSetView {
+                section,
+                endian: self.endian,
+            })
+    }
+
+    /// Returns the names of the emitter sets, where the name at index `i` is referenced by the emitter set handle `i + 1`.
+    ///
+    /// Names which are not valid UTF-8 are decoded lossily.
+    pub fn emitter_set_names(&self) -> Vec<String> {
+        self.emitter_sets()
+            .map(|emitter_set| emitter_set.name_lossy().into_owned())
+            .collect()
+    }
+}
+
+/// A read-only view of an emitter set section of a [`PtclFile`].
+#[derive(Debug, Clone, Copy)]
+pub struct EmitterSetView<'a> {
+    section: &'a Section,
+    endian: Endian,
+}
+
+impl<'a> EmitterSetView<'a> {
+    /// Returns the name of the emitter set.
+    pub fn name(&self) -> Result<&'a str, str::Utf8Error> {
+        fixed_str(&self.section.data, PtclFile::EMITTER_SET_NAME_OFFSET)
+    }
+
+    /// Returns the name of the emitter set, replacing invalid UTF-8 sequences.
+    pub fn name_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(fixed_bytes(
+            &self.section.data,
+            PtclFile::EMITTER_SET_NAME_OFFSET,
+        ))
+    }
+
+    /// Returns an iterator over the emitters of the emitter set.
+    pub fn emitters(&self) -> impl Iterator<Item = EmitterView<'a>> {
+        let endian = self.endian;
+
+        self.section
+            .children_of(&Section::EMITTER)
+            .map(move |section| EmitterView { section, endian })
+    }
+
+    /// Returns the underlying section.
+    pub fn section(&self) -> &'a Section {
+        self.section
+    }
+}
+
+/// A read-only view of an emitter section of a [`PtclFile`].
+#[derive(Debug, Clone, Copy)]
+pub struct EmitterView<'a> {
+    section: &'a Section,
+    endian: Endian,
+}
+
+impl<'a> EmitterView<'a> {
+    /// Returns the name of the emitter.
+    pub fn name(&self) -> Result<&'a str, str::Utf8Error> {
+        fixed_str(&self.section.data, PtclFile::EMITTER_NAME_OFFSET)
+    }
+
+    /// Returns the name of the emitter, replacing invalid UTF-8 sequences.
+    pub fn name_lossy(&self) -> Cow<'a, str> {
+        String::from_utf8_lossy(fixed_bytes(
+            &self.section.data,
+            PtclFile::EMITTER_NAME_OFFSET,
+        ))
+    }
+
+    /// Returns an iterator over the child emitters spawned by the emitter.
+    pub fn emitters(&self) -> impl Iterator<Item = EmitterView<'a>> {
+        let endian = self.endian;
+
+        self.section
+            .children_of(&Section::EMITTER)
+            .map(move |section| EmitterView { section, endian })
+    }
+
+    /// Returns the byte order of the resource, which the fields of the emitter are read with.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Returns the binary data of the emitter.
+    pub fn data(&self) -> &'a [u8] {
+        &self.section.data
+    }
+
+    /// Returns the underlying section.
+    pub fn section(&self) -> &'a Section {
+        self.section
+    }
+}
+
+/// Reads a nul-padded string of the fixed name length at the given offset.
+pub(crate) fn fixed_str(data: &[u8], offset: usize) -> Result<&str, str::Utf8Error> {
+    str::from_utf8(fixed_bytes(data, offset))
+}
+
+/// Returns the bytes of a nul-padded string of the fixed name length at the given offset, without the padding.
+pub(crate) fn fixed_bytes(data: &[u8], offset: usize) -> &[u8] {
+    let bytes = data
+        .get(offset..(offset + PtclFile::NAME_LENGTH).min(data.len()))
+        .unwrap_or_default();
+    let length = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+
+    &bytes[..length]
+}
+
+#[cfg(feature = "serde")]
+mod endian {
+    use binrw::Endian;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum ByteOrder {
+        Little,
+        Big,
+    }
+
+    pub fn serialize<S: Serializer>(endian: &Endian, serializer: S) -> Result<S::Ok, S::Error> {
+        match endian {
+            Endian::Little => ByteOrder::Little,
+            Endian::Big => ByteOrder::Big,
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Endian, D::Error> {
+        Ok(match ByteOrder::deserialize(deserializer)? {
+            ByteOrder::Little => Endian::Little,
+            ByteOrder::Big => Endian::Big,
+        })
+    }
+}
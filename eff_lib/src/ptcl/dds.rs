@@ -0,0 +1,178 @@
+use std::{fmt, io::Write};
+
+use ddsfile::{AlphaMode, D3D10ResourceDimension, Dds, DxgiFormat, NewDxgiParams};
+use tegra_swizzle::{
+    surface::{deswizzle_surface, BlockDim},
+    BlockHeight, SwizzleError,
+};
+
+use crate::PtclTexture;
+
+/// An error that can occur when converting a [`PtclTexture`] to DDS.
+#[derive(Debug)]
+pub enum DdsError {
+    /// The surface format of the texture has no DXGI equivalent.
+    UnsupportedFormat(u32),
+
+    /// The image data could not be deswizzled.
+    Swizzle(SwizzleError),
+
+    /// The DDS file could not be created or written.
+    Dds(ddsfile::Error),
+}
+
+impl fmt::Display for DdsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedFormat(format) => {
+                write!(f, "texture format {format:#06X} has no DXGI equivalent")
+            }
+            Self::Swizzle(error) => write!(f, "failed to deswizzle texture: {error}"),
+            Self::Dds(error) => write!(f, "failed to create DDS file: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for DdsError {}
+
+impl From<SwizzleError> for DdsError {
+    fn from(error: SwizzleError) -> Self {
+        Self::Swizzle(error)
+    }
+}
+
+impl From<ddsfile::Error> for DdsError {
+    fn from(error: ddsfile::Error) -> Self {
+        Self::Dds(error)
+    }
+}
+
+impl PtclTexture {
+    /// Tiling mode of image data stored in the block linear layout.
+    const TILE_MODE_BLOCK_LINEAR: u16 = 0;
+
+    /// Returns the DXGI format equivalent to the surface format of the texture, or `None` if there is none.
+    pub fn dxgi_format(&self) -> Option<DxgiFormat> {
+        match self.format {
+            0x0201 => Some(DxgiFormat::R8_UNorm),
+            0x0901 => Some(DxgiFormat::R8G8_UNorm),
+            0x0B01 => Some(DxgiFormat::R8G8B8A8_UNorm),
+            0x0B06 => Some(DxgiFormat::R8G8B8A8_UNorm_sRGB),
+            0x0C01 => Some(DxgiFormat::B8G8R8A8_UNorm),
+            0x0C06 => Some(DxgiFormat::B8G8R8A8_UNorm_sRGB),
+            0x1A01 => Some(DxgiFormat::BC1_UNorm),
+            0x1A06 => Some(DxgiFormat::BC1_UNorm_sRGB),
+            0x1B01 => Some(DxgiFormat::BC2_UNorm),
+            0x1B06 => Some(DxgiFormat::BC2_UNorm_sRGB),
+            0x1C01 => Some(DxgiFormat::BC3_UNorm),
+            0x1C06 => Some(DxgiFormat::BC3_UNorm_sRGB),
+            0x1D01 => Some(DxgiFormat::BC4_UNorm),
+            0x1D02 => Some(DxgiFormat::BC4_SNorm),
+            0x1E01 => Some(DxgiFormat::BC5_UNorm),
+            0x1E02 => Some(DxgiFormat::BC5_SNorm),
+            0x1F05 => Some(DxgiFormat::BC6H_SF16),
+            0x1F0A => Some(DxgiFormat::BC6H_UF16),
+            0x2001 => Some(DxgiFormat::BC7_UNorm),
+            0x2006 => Some(DxgiFormat::BC7_UNorm_sRGB),
+            _ => None,
+        }
+    }
+
+    /// Converts the texture to a DDS file, deswizzling the image data of every mipmap and array layer.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::PtclTexture;
+    ///
+    /// let texture = PtclTexture {
+    ///     name: "smoke".to_string(),
+    ///     width: 4,
+    ///     height: 4,
+    ///     depth: 1,
+    ///     mip_count: 1,
+    ///     array_count: 1,
+    ///     format: 0x0B01,
+    ///     tile_mode: 0,
+    ///     block_height_log2: 0,
+    ///     mip_offsets: vec![0],
+    ///     data: vec![0xFF; 0x200],
+    /// };
+    ///
+    /// let dds = texture.to_dds().unwrap();
+    ///
+    /// assert_eq!(dds.get_width(), 4);
+    /// assert_eq!(dds.get_height(), 4);
+    /// assert_eq!(dds.data, [0xFF; 0x40]);
+    /// ```
+    pub fn to_dds(&self) -> Result<Dds, DdsError> {
+        let format = self
+            .dxgi_format()
+            .ok_or(DdsError::UnsupportedFormat(self.format))?;
+        let (block_dim, bytes_per_pixel) = format_block_info(format);
+        let depth = self.depth.max(1);
+        let mip_count = self.mip_count.max(1) as u32;
+        let array_count = self.array_count.max(1);
+        let mut dds = Dds::new_dxgi(NewDxgiParams {
+            height: self.height,
+            width: self.width,
+            depth: (depth > 1).then_some(depth),
+            format,
+            mipmap_levels: (mip_count > 1).then_some(mip_count),
+            array_layers: (array_count > 1).then_some(array_count),
+            caps2: None,
+            is_cubemap: false,
+            resource_dimension: if depth > 1 {
+                D3D10ResourceDimension::Texture3D
+            } else {
+                D3D10ResourceDimension::Texture2D
+            },
+            alpha_mode: AlphaMode::Unknown,
+        })?;
+
+        // Image data which is not block linear is already stored row by row.
+        dds.data = if self.tile_mode == Self::TILE_MODE_BLOCK_LINEAR {
+            deswizzle_surface(
+                self.width as usize,
+                self.height as usize,
+                depth as usize,
+                &self.data,
+                block_dim,
+                BlockHeight::new(1 << self.block_height_log2.min(5)),
+                bytes_per_pixel,
+                mip_count as usize,
+                array_count as usize,
+            )?
+        } else {
+            self.data.clone()
+        };
+
+        Ok(dds)
+    }
+
+    /// Writes the texture as a DDS file to the given writer.
+    pub fn write_dds<W: Write>(&self, writer: &mut W) -> Result<(), DdsError> {
+        self.to_dds()?.write(writer)?;
+
+        Ok(())
+    }
+}
+
+/// Returns the block dimensions and the size in bytes of a block of the given format.
+fn format_block_info(format: DxgiFormat) -> (BlockDim, usize) {
+    match format {
+        DxgiFormat::R8_UNorm => (BlockDim::uncompressed(), 1),
+        DxgiFormat::R8G8_UNorm => (BlockDim::uncompressed(), 2),
+        DxgiFormat::R8G8B8A8_UNorm
+        | DxgiFormat::R8G8B8A8_UNorm_sRGB
+        | DxgiFormat::B8G8R8A8_UNorm
+        | DxgiFormat::B8G8R8A8_UNorm_sRGB => (BlockDim::uncompressed(), 4),
+        DxgiFormat::BC1_UNorm
+        | DxgiFormat::BC1_UNorm_sRGB
+        | DxgiFormat::BC4_UNorm
+        | DxgiFormat::BC4_SNorm => (BlockDim::block_4x4(), 8),
+        _ => (BlockDim::block_4x4(), 16),
+    }
+}
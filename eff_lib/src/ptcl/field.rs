@@ -0,0 +1,163 @@
+use std::io::Cursor;
+
+use binrw::{binrw, BinRead, BinReaderExt, BinResult, BinWrite, Endian};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::section::write_at;
+use crate::{EmitterView, Section};
+
+/// A field of an emitter, which alters the motion of its particles.
+///
+/// Each field is stored in its own child section of the emitter.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_lib::{EmitterField, GravityField, Section};
+///
+/// let field = EmitterField::Gravity(GravityField {
+///     direction: [0.0, -1.0, 0.0],
+///     strength: 0.05,
+/// });
+///
+/// let section = field.to_section().unwrap();
+///
+/// assert_eq!(section.magic, Section::GRAVITY_FIELD);
+/// assert_eq!(EmitterField::from_section(&section).unwrap(), Some(field));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EmitterField {
+    /// A constant acceleration, such as gravity.
+    Gravity(GravityField),
+
+    /// A random displacement applied at regular intervals.
+    Turbulence(TurbulenceField),
+
+    /// An attraction towards a point.
+    Magnet(MagnetField),
+}
+
+/// A field which accelerates particles in a constant direction.
+#[binrw]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct GravityField {
+    /// Direction of the acceleration.
+    pub direction: [f32; 3],
+
+    /// Acceleration added to the velocity of particles each frame.
+    pub strength: f32,
+}
+
+/// A field which randomly displaces particles at regular intervals.
+#[binrw]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct TurbulenceField {
+    /// Largest random change of velocity on each axis.
+    pub strength: [f32; 3],
+
+    /// Number of frames between random changes.
+    pub interval: i32,
+}
+
+/// A field which attracts particles towards a point.
+#[binrw]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MagnetField {
+    /// Position particles are attracted towards, relative to the emitter.
+    pub position: [f32; 3],
+
+    /// Strength of the attraction.
+    pub strength: f32,
+
+    /// Whether the position follows the emitter as it moves.
+    #[br(map = |value: u32| value != 0)]
+    #[bw(map = |value: &bool| *value as u32)]
+    pub follow_emitter: bool,
+}
+
+impl EmitterField {
+    /// Returns the identifier of the section the field is stored in.
+    pub fn magic(&self) -> [u8; 4] {
+        match self {
+            Self::Gravity(_) => Section::GRAVITY_FIELD,
+            Self::Turbulence(_) => Section::TURBULENCE_FIELD,
+            Self::Magnet(_) => Section::MAGNET_FIELD,
+        }
+    }
+
+    /// Reads the field stored in the given section of a little-endian resource, or returns `None` if the section is not a known field.
+    pub fn from_section(section: &Section) -> BinResult<Option<Self>> {
+        Self::read_section(section, Endian::Little)
+    }
+
+    fn read_section(section: &Section, endian: Endian) -> BinResult<Option<Self>> {
+        Ok(match section.magic {
+            Section::GRAVITY_FIELD => Some(Self::Gravity(read_block(&section.data, endian)?)),
+            Section::TURBULENCE_FIELD => Some(Self::Turbulence(read_block(&section.data, endian)?)),
+            Section::MAGNET_FIELD => Some(Self::Magnet(read_block(&section.data, endian)?)),
+            _ => None,
+        })
+    }
+
+    /// Writes the field into the little-endian binary data of a field section, leaving the rest of the data untouched.
+    ///
+    /// Data which is too short to hold the field is extended with zeros.
+    pub fn write_to_section_data(&self, data: &mut Vec<u8>) -> BinResult<()> {
+        match self {
+            Self::Gravity(field) => write_block(field, data, Endian::Little),
+            Self::Turbulence(field) => write_block(field, data, Endian::Little),
+            Self::Magnet(field) => write_block(field, data, Endian::Little),
+        }
+    }
+
+    /// Constructs a new section holding the field.
+    pub fn to_section(&self) -> BinResult<Section> {
+        let mut data = Vec::new();
+
+        self.write_to_section_data(&mut data)?;
+
+        Ok(Section::new(self.magic(), data))
+    }
+}
+
+impl EmitterView<'_> {
+    /// Reads the fields of the emitter, in the order of its child sections.
+    pub fn fields(&self) -> BinResult<Vec<EmitterField>> {
+        self.section()
+            .children
+            .iter()
+            .filter_map(|section| EmitterField::read_section(section, self.endian()).transpose())
+            .collect()
+    }
+}
+
+/// Reads a block from the start of the binary data of a field section, with the given byte order.
+fn read_block<T>(data: &[u8], endian: Endian) -> BinResult<T>
+where
+    T: BinRead,
+    for<'a> T::Args<'a>: Default,
+{
+    Cursor::new(data).read_type(endian)
+}
+
+/// Writes a block over the start of the binary data of a field section with the given byte order, extending the data with zeros if needed.
+fn write_block<T>(value: &T, data: &mut Vec<u8>, endian: Endian) -> BinResult<()>
+where
+    T: BinWrite,
+    for<'a> T::Args<'a>: Default,
+{
+    let mut writer = Cursor::new(Vec::new());
+
+    value.write_options(&mut writer, endian, Default::default())?;
+    write_at(data, 0, writer.get_ref());
+
+    Ok(())
+}
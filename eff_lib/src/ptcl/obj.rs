@@ -0,0 +1,267 @@
+use std::{collections::HashMap, fmt, fmt::Write};
+
+use crate::{AttributeKind, PtclPrimitive, VertexAttribute};
+
+/// An error that can occur when importing a [`PtclPrimitive`] from OBJ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjError {
+    /// A line could not be parsed.
+    InvalidLine(usize),
+
+    /// A face on the given line references a vertex, texture coordinate, or normal which does not exist.
+    InvalidIndex(usize),
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidLine(line) => write!(f, "invalid OBJ statement on line {line}"),
+            Self::InvalidIndex(line) => {
+                write!(f, "face on line {line} references a missing element")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+impl PtclPrimitive {
+    /// Converts the primitive to a Wavefront OBJ mesh.
+    ///
+    /// Only the position, normal, and first texture coordinate attributes are exported.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::{AttributeKind, PtclPrimitive, VertexAttribute};
+    ///
+    /// let triangle = PtclPrimitive {
+    ///     attributes: vec![VertexAttribute {
+    ///         kind: AttributeKind::Position,
+    ///         component_count: 3,
+    ///         values: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+    ///     }],
+    ///     indices: vec![0, 1, 2],
+    /// };
+    ///
+    /// let obj = triangle.to_obj();
+    ///
+    /// assert_eq!(obj, "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n");
+    /// assert_eq!(PtclPrimitive::from_obj(&obj).unwrap(), triangle);
+    /// ```
+    pub fn to_obj(&self) -> String {
+        let mut obj = String::new();
+        let positions = self.attribute(AttributeKind::Position);
+        let tex_coords = self.attribute(AttributeKind::TexCoord0);
+        let normals = self.attribute(AttributeKind::Normal);
+
+        for (prefix, attribute, component_count) in [
+            ("v", positions, 3),
+            ("vt", tex_coords, 2),
+            ("vn", normals, 3),
+        ] {
+            let Some(attribute) = attribute else {
+                continue;
+            };
+
+            for vertex in 0..attribute.vertex_count() {
+                obj.push_str(prefix);
+
+                for component in 0..component_count {
+                    let value = if component < attribute.component_count as usize {
+                        attribute.values[vertex * attribute.component_count as usize + component]
+                    } else {
+                        0.0
+                    };
+
+                    write!(obj, " {value}").unwrap();
+                }
+
+                obj.push('\n');
+            }
+        }
+
+        for triangle in self.indices.chunks_exact(3) {
+            obj.push('f');
+
+            for index in triangle {
+                let index = index + 1;
+
+                match (tex_coords.is_some(), normals.is_some()) {
+                    (false, false) => write!(obj, " {index}"),
+                    (true, false) => write!(obj, " {index}/{index}"),
+                    (false, true) => write!(obj, " {index}//{index}"),
+                    (true, true) => write!(obj, " {index}/{index}/{index}"),
+                }
+                .unwrap();
+            }
+
+            obj.push('\n');
+        }
+
+        obj
+    }
+
+    /// Converts a Wavefront OBJ mesh to a primitive.
+    ///
+    /// Faces with more than three vertices are split into triangles, and statements other than vertices, texture coordinates, normals, and faces are ignored.
+    pub fn from_obj(obj: &str) -> Result<Self, ObjError> {
+        let mut positions = Vec::new();
+        let mut tex_coords = Vec::new();
+        let mut normals = Vec::new();
+        let mut corners = Vec::new();
+
+        for (i, line) in obj.lines().enumerate() {
+            let line_number = i + 1;
+            let mut tokens = line.split_whitespace();
+
+            match tokens.next() {
+                Some("v") => positions.push(parse_values::<3>(tokens, line_number)?),
+                Some("vt") => tex_coords.push(parse_values::<2>(tokens, line_number)?),
+                Some("vn") => normals.push(parse_values::<3>(tokens, line_number)?),
+                Some("f") => {
+                    let face = tokens
+                        .map(|token| {
+                            parse_corner(
+                                token,
+                                [positions.len(), tex_coords.len(), normals.len()],
+                                line_number,
+                            )
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    if face.len() < 3 {
+                        return Err(ObjError::InvalidLine(line_number));
+                    }
+
+                    for j in 1..face.len() - 1 {
+                        corners.extend([face[0], face[j], face[j + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // OBJ indexes each element separately, so every distinct combination becomes a vertex.
+        let has_tex_coords = corners.iter().any(|corner| corner[1].is_some());
+        let has_normals = corners.iter().any(|corner| corner[2].is_some());
+        let mut vertices = HashMap::new();
+        let mut position_values = Vec::new();
+        let mut tex_coord_values = Vec::new();
+        let mut normal_values = Vec::new();
+        let mut indices = Vec::with_capacity(corners.len());
+
+        for corner in corners {
+            let next_index = vertices.len() as u32;
+            let index = *vertices.entry(corner).or_insert_with(|| {
+                position_values.extend(corner[0].map_or([0.0; 3], |i| positions[i]));
+
+                if has_tex_coords {
+                    tex_coord_values.extend(corner[1].map_or([0.0; 2], |i| tex_coords[i]));
+                }
+
+                if has_normals {
+                    normal_values.extend(corner[2].map_or([0.0; 3], |i| normals[i]));
+                }
+
+                next_index
+            });
+
+            indices.push(index);
+        }
+
+        let mut attributes = vec![VertexAttribute {
+            kind: AttributeKind::Position,
+            component_count: 3,
+            values: position_values,
+        }];
+
+        if has_normals {
+            attributes.push(VertexAttribute {
+                kind: AttributeKind::Normal,
+                component_count: 3,
+                values: normal_values,
+            });
+        }
+
+        if has_tex_coords {
+            attributes.push(VertexAttribute {
+                kind: AttributeKind::TexCoord0,
+                component_count: 2,
+                values: tex_coord_values,
+            });
+        }
+
+        Ok(Self {
+            attributes,
+            indices,
+        })
+    }
+
+    fn attribute(&self, kind: AttributeKind) -> Option<&VertexAttribute> {
+        self.attributes
+            .iter()
+            .find(|attribute| attribute.kind == kind && attribute.component_count > 0)
+    }
+}
+
+/// Parses the first `N` values of a statement, ignoring any optional values after them.
+fn parse_values<'a, const N: usize>(
+    mut tokens: impl Iterator<Item = &'a str>,
+    line: usize,
+) -> Result<[f32; N], ObjError> {
+    let mut values = [0.0; N];
+
+    for value in &mut values {
+        *value = tokens
+            .next()
+            .and_then(|token| token.parse().ok())
+            .ok_or(ObjError::InvalidLine(line))?;
+    }
+
+    Ok(values)
+}
+
+/// Parses a face corner of the form `v`, `v/vt`, `v//vn`, or `v/vt/vn` into zero-based indices.
+fn parse_corner(
+    token: &str,
+    counts: [usize; 3],
+    line: usize,
+) -> Result<[Option<usize>; 3], ObjError> {
+    let mut corner = [None; 3];
+
+    for (i, index) in token.split('/').enumerate() {
+        if i >= 3 {
+            return Err(ObjError::InvalidLine(line));
+        }
+
+        if index.is_empty() && i > 0 {
+            continue;
+        }
+
+        let index = index
+            .parse::<i64>()
+            .map_err(|_| ObjError::InvalidLine(line))?;
+
+        // Negative indices count back from the most recent element.
+        let resolved = match index {
+            1.. => index - 1,
+            ..=-1 => counts[i] as i64 + index,
+            0 => return Err(ObjError::InvalidIndex(line)),
+        };
+
+        if resolved < 0 || resolved >= counts[i] as i64 {
+            return Err(ObjError::InvalidIndex(line));
+        }
+
+        corner[i] = Some(resolved as usize);
+    }
+
+    if corner[0].is_none() {
+        return Err(ObjError::InvalidLine(line));
+    }
+
+    Ok(corner)
+}
@@ -0,0 +1,166 @@
+use std::io::Cursor;
+
+use binrw::{binrw, BinReaderExt, BinResult, BinWrite};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PtclFile, Section};
+
+/// A primitive mesh stored in the primitive array of a [`PtclFile`], which emitters can use as the particle shape.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_lib::{AttributeKind, PtclFile, PtclHeader, PtclPrimitive, Section, VertexAttribute};
+///
+/// let triangle = PtclPrimitive {
+///     attributes: vec![VertexAttribute {
+///         kind: AttributeKind::Position,
+///         component_count: 3,
+///         values: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+///     }],
+///     indices: vec![0, 1, 2],
+/// };
+///
+/// let mut ptcl = PtclFile::new(PtclHeader::default());
+/// let mut primitive_array = Section::new(Section::PRIMITIVE_ARRAY, Vec::new());
+/// primitive_array.children.push(Section::new(Section::PRIMITIVE, Vec::new()));
+/// ptcl.sections.push(primitive_array);
+///
+/// ptcl.replace_primitive(0, &triangle).unwrap();
+///
+/// assert_eq!(ptcl.primitives().unwrap(), [triangle]);
+/// ```
+#[binrw]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PtclPrimitive {
+    #[br(temp)]
+    #[bw(calc = attributes.len() as u32)]
+    attribute_count: u32,
+
+    #[br(temp)]
+    #[bw(calc = attributes.first().map_or(0, VertexAttribute::vertex_count) as u32)]
+    vertex_count: u32,
+
+    #[br(temp)]
+    #[bw(calc = indices.len() as u32)]
+    index_count: u32,
+
+    #[br(temp)]
+    #[bw(calc = 0)]
+    _padding: u32,
+
+    /// Collection of vertex attributes, each holding one value for every vertex.
+    #[br(args { count: attribute_count as usize, inner: (vertex_count,) })]
+    #[bw(assert(
+        vertex_counts_match(attributes),
+        "vertex attributes have different vertex counts"
+    ))]
+    pub attributes: Vec<VertexAttribute>,
+
+    /// Collection of vertex indices, where every three indices form a triangle.
+    #[br(count = index_count)]
+    pub indices: Vec<u32>,
+}
+
+/// A vertex attribute of a [`PtclPrimitive`].
+#[binrw]
+#[br(import(vertex_count: u32))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexAttribute {
+    /// Kind of data held by the attribute.
+    pub kind: AttributeKind,
+
+    /// Number of values for each vertex.
+    pub component_count: u32,
+
+    /// Values of every vertex, with the values of each vertex stored together.
+    #[br(count = vertex_count * component_count)]
+    pub values: Vec<f32>,
+}
+
+/// The kind of data held by a [`VertexAttribute`].
+#[binrw]
+#[brw(repr = u32)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AttributeKind {
+    Position = 0,
+    Normal = 1,
+    Tangent = 2,
+    Color = 3,
+    TexCoord0 = 4,
+    TexCoord1 = 5,
+}
+
+impl PtclPrimitive {
+    /// Returns the number of vertices, based on the first vertex attribute.
+    pub fn vertex_count(&self) -> usize {
+        self.attributes
+            .first()
+            .map_or(0, VertexAttribute::vertex_count)
+    }
+}
+
+impl VertexAttribute {
+    /// Returns the number of vertices with values for the attribute.
+    pub fn vertex_count(&self) -> usize {
+        match self.component_count {
+            0 => 0,
+            component_count => self.values.len() / component_count as usize,
+        }
+    }
+}
+
+impl PtclFile {
+    /// Returns the primitives stored in the primitive array, in the order they are indexed by emitters.
+    ///
+    /// Resources without a primitive array have no primitives.
+    pub fn primitives(&self) -> BinResult<Vec<PtclPrimitive>> {
+        self.primitive_sections()
+            .map(|section| Cursor::new(&section.data).read_type(self.endian))
+            .collect()
+    }
+
+    /// Replaces the primitive at the given index in the primitive array.
+    pub fn replace_primitive(&mut self, index: usize, primitive: &PtclPrimitive) -> BinResult<()> {
+        let mut writer = Cursor::new(Vec::new());
+
+        primitive.write_options(&mut writer, self.endian, ())?;
+
+        let section = self
+            .section_mut(&Section::PRIMITIVE_ARRAY)
+            .and_then(|array| {
+                array
+                    .children
+                    .iter_mut()
+                    .filter(|child| child.magic == Section::PRIMITIVE)
+                    .nth(index)
+            })
+            .ok_or_else(|| binrw::Error::AssertFail {
+                pos: 0,
+                message: format!("primitive index {index} is out of range"),
+            })?;
+
+        section.data = writer.into_inner();
+
+        Ok(())
+    }
+
+    fn primitive_sections(&self) -> impl Iterator<Item = &Section> {
+        self.section(&Section::PRIMITIVE_ARRAY)
+            .into_iter()
+            .flat_map(|array| array.children_of(&Section::PRIMITIVE))
+    }
+}
+
+fn vertex_counts_match(attributes: &[VertexAttribute]) -> bool {
+    attributes
+        .windows(2)
+        .all(|pair| pair[0].vertex_count() == pair[1].vertex_count())
+}
@@ -0,0 +1,384 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use binrw::{binrw, BinReaderExt, BinResult, BinWrite, Endian};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A section of a [`PtclFile`](crate::PtclFile), holding a binary data block and any number of child sections.
+///
+/// Sections which are not otherwise understood are still read and written with their data and children intact.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone)]
+pub struct Section {
+    /// Four-character identifier of the section.
+    pub magic: [u8; 4],
+
+    /// Data buffer for the binary data of the section.
+    pub data: Vec<u8>,
+
+    /// Collection of child sections.
+    pub children: Vec<Section>,
+
+    /// Required alignment of the binary data within the resource, in bytes.
+    pub alignment: u32,
+
+    // TODO: Determine the purpose of this field.
+    pub unk1: u32,
+
+    // TODO: Determine the purpose of this field.
+    pub unk2: u16,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) layout: Option<SectionLayout>,
+}
+
+/// The placement of a section as it was read, used to write unchanged sections back to the same offsets.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct SectionLayout {
+    pub position: u64,
+    pub binary_offset: u32,
+    pub child_offset: Option<u32>,
+    pub next_offset: Option<u32>,
+}
+
+#[binrw]
+#[derive(Debug)]
+pub(crate) struct SectionHeader {
+    pub magic: [u8; 4],
+    pub size: u32,
+    pub child_offset: u32,
+    pub next_offset: u32,
+    pub unk1: u32,
+    pub binary_offset: u32,
+    pub padding: u32,
+    pub child_count: u16,
+    pub unk2: u16,
+}
+
+impl Section {
+    /// Identifier of the emitter set array section.
+    pub const EMITTER_SET_ARRAY: [u8; 4] = *b"ESTA";
+
+    /// Identifier of an emitter set section.
+    pub const EMITTER_SET: [u8; 4] = *b"ESET";
+
+    /// Identifier of an emitter section.
+    pub const EMITTER: [u8; 4] = *b"EMTR";
+
+    /// Identifier of the texture array section.
+    pub const TEXTURE_ARRAY: [u8; 4] = *b"GRTF";
+
+    /// Identifier of the shader array section.
+    pub const SHADER_ARRAY: [u8; 4] = *b"GRSN";
+
+    /// Identifier of the compute shader array section.
+    pub const COMPUTE_SHADER_ARRAY: [u8; 4] = *b"GRSC";
+
+    /// Identifier of the primitive array section.
+    pub const PRIMITIVE_ARRAY: [u8; 4] = *b"PRMA";
+
+    /// Identifier of a primitive section.
+    pub const PRIMITIVE: [u8; 4] = *b"PRIM";
+
+    /// Identifier of a gravity field section of an emitter.
+    pub const GRAVITY_FIELD: [u8; 4] = *b"FPAD";
+
+    /// Identifier of a turbulence field section of an emitter.
+    pub const TURBULENCE_FIELD: [u8; 4] = *b"FRND";
+
+    /// Identifier of a magnet field section of an emitter.
+    pub const MAGNET_FIELD: [u8; 4] = *b"FMAG";
+
+    pub(crate) const HEADER_SIZE: u32 = 0x20;
+    pub(crate) const NONE_OFFSET: u32 = u32::MAX;
+    pub(crate) const DEFAULT_ALIGNMENT: u32 = 0x10;
+    pub(crate) const MAX_ALIGNMENT: u32 = 0x1000;
+
+    /// Constructs a new section with the given identifier and binary data, without children.
+    pub fn new(magic: [u8; 4], data: Vec<u8>) -> Self {
+        Self {
+            magic,
+            data,
+            children: Vec::new(),
+            alignment: Self::DEFAULT_ALIGNMENT,
+            unk1: Self::NONE_OFFSET,
+            unk2: 0,
+            layout: None,
+        }
+    }
+
+    /// Returns the first child section with the given identifier.
+    pub fn child(&self, magic: &[u8; 4]) -> Option<&Section> {
+        self.children.iter().find(|child| &child.magic == magic)
+    }
+
+    /// Returns the first child section with the given identifier.
+    pub fn child_mut(&mut self, magic: &[u8; 4]) -> Option<&mut Section> {
+        self.children.iter_mut().find(|child| &child.magic == magic)
+    }
+
+    /// Returns an iterator over the child sections with the given identifier.
+    pub fn children_of<'a>(&'a self, magic: &'a [u8; 4]) -> impl Iterator<Item = &'a Section> {
+        self.children
+            .iter()
+            .filter(move |child| &child.magic == magic)
+    }
+
+    /// Returns the identifier of the section as a string, if it is valid UTF-8.
+    pub fn magic_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.magic).ok()
+    }
+
+    /// Reads the chain of sibling sections starting at the given position.
+    pub(crate) fn read_chain<R: Read + Seek>(
+        reader: &mut R,
+        position: u64,
+        endian: Endian,
+    ) -> BinResult<Vec<Self>> {
+        let mut sections = Vec::new();
+        let mut position = position;
+        let stream_length = reader.seek(SeekFrom::End(0))?;
+
+        loop {
+            reader.seek(SeekFrom::Start(position))?;
+
+            let header = reader.read_type::<SectionHeader>(endian)?;
+            let data_position = position + header.binary_offset as u64;
+
+            // Check the size against the stream before allocating, since it comes from untrusted data.
+            if data_position + header.size as u64 > stream_length {
+                return Err(binrw::Error::AssertFail {
+                    pos: position,
+                    message: format!(
+                        "section `{}` data of size {:#X} runs past the end of the resource",
+                        String::from_utf8_lossy(&header.magic),
+                        header.size
+                    ),
+                });
+            }
+
+            reader.seek(SeekFrom::Start(data_position))?;
+
+            let mut data = vec![0; header.size as usize];
+
+            reader.read_exact(&mut data)?;
+
+            let child_offset = offset(header.child_offset, position)?;
+            let next_offset = offset(header.next_offset, position)?;
+            let children = match child_offset {
+                Some(child_offset) => {
+                    Self::read_chain(reader, position + child_offset as u64, endian)?
+                }
+                None => Vec::new(),
+            };
+
+            if children.len() != header.child_count as usize {
+                return Err(binrw::Error::AssertFail {
+                    pos: position,
+                    message: format!(
+                        "section `{}` declares {} children, but {} were found",
+                        String::from_utf8_lossy(&header.magic),
+                        header.child_count,
+                        children.len()
+                    ),
+                });
+            }
+
+            sections.push(Self {
+                magic: header.magic,
+                data,
+                children,
+                alignment: inferred_alignment(data_position),
+                unk1: header.unk1,
+                unk2: header.unk2,
+                layout: Some(SectionLayout {
+                    position,
+                    binary_offset: header.binary_offset,
+                    child_offset,
+                    next_offset,
+                }),
+            });
+
+            match next_offset {
+                Some(next_offset) => position += next_offset as u64,
+                None => return Ok(sections),
+            }
+        }
+    }
+
+    /// Discards the recorded placement of the section and its children, so they are laid out anew when written.
+    pub(crate) fn clear_layout(&mut self) {
+        self.layout = None;
+
+        for child in &mut self.children {
+            child.clear_layout();
+        }
+    }
+
+    /// Checks that the binary data of every section in the written chain is aligned as required by the corresponding section.
+    pub(crate) fn verify_chain(sections: &[Self], written: &[Self]) -> BinResult<()> {
+        for (section, written) in sections.iter().zip(written) {
+            let layout = written.layout.unwrap();
+            let data_position = layout.position + layout.binary_offset as u64;
+
+            if !data_position.is_multiple_of((section.alignment as u64).max(1)) {
+                return Err(binrw::Error::AssertFail {
+                    pos: layout.position,
+                    message: format!(
+                        "section `{}` data at {:#X} is not aligned to {:#X}",
+                        String::from_utf8_lossy(&section.magic),
+                        data_position,
+                        section.alignment
+                    ),
+                });
+            }
+
+            Self::verify_chain(&section.children, &written.children)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the chain of sibling sections starting at the given position into the buffer, returning the end of the last section.
+    pub(crate) fn write_chain(
+        sections: &[Self],
+        buffer: &mut Vec<u8>,
+        position: u64,
+        endian: Endian,
+    ) -> u64 {
+        let mut position = position;
+        let mut end = position;
+
+        for (i, section) in sections.iter().enumerate() {
+            let (subtree_end, next_position) = section.write(buffer, position, endian);
+
+            end = subtree_end;
+
+            if i + 1 < sections.len() {
+                let next_offset = (next_position - position) as u32;
+
+                write_at(buffer, position + 0xC, &u32_bytes(next_offset, endian));
+                position = next_position;
+            }
+        }
+
+        end
+    }
+
+    /// Writes the section and its children at the given position, returning the end of the section and the position of its next sibling.
+    fn write(&self, buffer: &mut Vec<u8>, position: u64, endian: Endian) -> (u64, u64) {
+        let layout = self.layout.filter(|layout| layout.position == position);
+        let header_end = position + Self::HEADER_SIZE as u64;
+        let data_position = layout
+            .map(|layout| position + layout.binary_offset as u64)
+            .filter(|data_position| {
+                *data_position >= header_end
+                    && data_position.is_multiple_of((self.alignment as u64).max(1))
+            })
+            .unwrap_or_else(|| align(header_end, self.alignment as u64));
+        let data_end = data_position + self.data.len() as u64;
+        let child_position = layout
+            .and_then(|layout| layout.child_offset)
+            .map(|child_offset| position + child_offset as u64)
+            .filter(|child_position| *child_position >= data_end)
+            .unwrap_or_else(|| align(data_end, Self::DEFAULT_ALIGNMENT as u64));
+        let end = if self.children.is_empty() {
+            data_end
+        } else {
+            Self::write_chain(&self.children, buffer, child_position, endian)
+        };
+        let next_position = layout
+            .and_then(|layout| layout.next_offset)
+            .map(|next_offset| position + next_offset as u64)
+            .filter(|next_position| *next_position >= end)
+            .unwrap_or_else(|| align(end, Self::DEFAULT_ALIGNMENT as u64));
+
+        let header = SectionHeader {
+            magic: self.magic,
+            size: self.data.len() as u32,
+            child_offset: if self.children.is_empty() {
+                Self::NONE_OFFSET
+            } else {
+                (child_position - position) as u32
+            },
+            next_offset: Self::NONE_OFFSET,
+            unk1: self.unk1,
+            binary_offset: (data_position - position) as u32,
+            padding: 0,
+            child_count: self.children.len() as u16,
+            unk2: self.unk2,
+        };
+
+        write_at(buffer, position, &header.to_bytes(endian));
+        write_at(buffer, data_position, &self.data);
+
+        (end, next_position)
+    }
+}
+
+impl PartialEq for Section {
+    fn eq(&self, other: &Self) -> bool {
+        self.magic == other.magic
+            && self.data == other.data
+            && self.children == other.children
+            && self.alignment == other.alignment
+            && self.unk1 == other.unk1
+            && self.unk2 == other.unk2
+    }
+}
+
+impl SectionHeader {
+    fn to_bytes(&self, endian: Endian) -> Vec<u8> {
+        let mut writer = std::io::Cursor::new(Vec::new());
+
+        self.write_options(&mut writer, endian, ()).unwrap();
+        writer.into_inner()
+    }
+}
+
+/// Converts a relative offset from the header of a section, where the maximum value means there is none.
+fn offset(offset: u32, position: u64) -> BinResult<Option<u32>> {
+    match offset {
+        Section::NONE_OFFSET => Ok(None),
+        0 => Err(binrw::Error::AssertFail {
+            pos: position,
+            message: "section offset must not point to itself".to_string(),
+        }),
+        offset => Ok(Some(offset)),
+    }
+}
+
+/// Returns the largest power of two dividing the given position, up to the maximum section alignment.
+fn inferred_alignment(position: u64) -> u32 {
+    if position == 0 {
+        return Section::MAX_ALIGNMENT;
+    }
+
+    (1 << position
+        .trailing_zeros()
+        .min(Section::MAX_ALIGNMENT.ilog2())) as u32
+}
+
+pub(crate) fn align(position: u64, alignment: u64) -> u64 {
+    position.next_multiple_of(alignment.max(1))
+}
+
+/// Returns the bytes of the given value in the given byte order.
+fn u32_bytes(value: u32, endian: Endian) -> [u8; 4] {
+    match endian {
+        Endian::Big => value.to_be_bytes(),
+        Endian::Little => value.to_le_bytes(),
+    }
+}
+
+pub(crate) fn write_at(buffer: &mut Vec<u8>, position: u64, bytes: &[u8]) {
+    let start = position as usize;
+    let end = start + bytes.len();
+
+    if buffer.len() < end {
+        buffer.resize(end, 0);
+    }
+
+    buffer[start..end].copy_from_slice(bytes);
+}
@@ -0,0 +1,73 @@
+use crate::{PtclFile, Section};
+
+/// A shader binary or shader parameter block stored in a shader array of a [`PtclFile`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtclShader<'a> {
+    /// Path of the section holding the block, such as `GRSN` or `GRSN/BNSH[0]`.
+    ///
+    /// Each section below a shader array is identified by its identifier and its index among its siblings.
+    pub identifier: String,
+
+    /// Binary data of the section.
+    pub data: &'a [u8],
+}
+
+impl PtclFile {
+    /// Returns every shader binary and shader parameter block in the shader arrays, with each section before its children.
+    ///
+    /// Sections without binary data are skipped, but their children are still visited.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::{PtclFile, PtclHeader, Section};
+    ///
+    /// let mut shader_array = Section::new(Section::SHADER_ARRAY, Vec::new());
+    /// shader_array.children.push(Section::new(*b"BNSH", vec![1; 4]));
+    ///
+    /// let mut ptcl = PtclFile::new(PtclHeader::default());
+    /// ptcl.sections.push(shader_array);
+    ///
+    /// let shaders = ptcl.shaders();
+    ///
+    /// assert_eq!(shaders.len(), 1);
+    /// assert_eq!(shaders[0].identifier, "GRSN/BNSH[0]");
+    /// assert_eq!(shaders[0].data, [1; 4]);
+    /// ```
+    pub fn shaders(&self) -> Vec<PtclShader<'_>> {
+        let mut shaders = Vec::new();
+
+        for section in &self.sections {
+            if section.magic == Section::SHADER_ARRAY
+                || section.magic == Section::COMPUTE_SHADER_ARRAY
+            {
+                let identifier = String::from_utf8_lossy(&section.magic).into_owned();
+
+                collect_shaders(section, identifier, &mut shaders);
+            }
+        }
+
+        shaders
+    }
+}
+
+fn collect_shaders<'a>(
+    section: &'a Section,
+    identifier: String,
+    shaders: &mut Vec<PtclShader<'a>>,
+) {
+    if !section.data.is_empty() {
+        shaders.push(PtclShader {
+            identifier: identifier.clone(),
+            data: &section.data,
+        });
+    }
+
+    for (i, child) in section.children.iter().enumerate() {
+        let magic = String::from_utf8_lossy(&child.magic);
+
+        collect_shaders(child, format!("{identifier}/{magic}[{i}]"), shaders);
+    }
+}
@@ -0,0 +1,752 @@
+use std::io::{Cursor, Seek, SeekFrom};
+
+use binrw::{binread, BinReaderExt, BinResult};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::section::write_at;
+use crate::{PtclFile, Section};
+
+/// A texture stored in the texture array of a [`PtclFile`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PtclTexture {
+    /// Name of the texture.
+    pub name: String,
+
+    /// Width of the texture in pixels.
+    pub width: u32,
+
+    /// Height of the texture in pixels.
+    pub height: u32,
+
+    /// Depth of the texture in pixels.
+    pub depth: u32,
+
+    /// Number of mipmaps, including the base level.
+    pub mip_count: u16,
+
+    /// Number of array layers.
+    pub array_count: u32,
+
+    /// Surface format of the texture, as stored in the texture info block.
+    pub format: u32,
+
+    /// Tiling mode of the texture data.
+    pub tile_mode: u16,
+
+    /// Base-2 logarithm of the height in GOBs of a block of the swizzled texture data.
+    pub block_height_log2: u32,
+
+    /// Offsets of the image data of each mipmap from the start of the image data.
+    pub mip_offsets: Vec<u64>,
+
+    /// Data buffer for the swizzled image data of every mipmap and array layer.
+    pub data: Vec<u8>,
+}
+
+/// The container header of the texture array data.
+#[binread]
+#[br(little, magic = b"BNTX")]
+#[derive(Debug)]
+pub(crate) struct TextureContainerHeader {
+    #[br(pad_before = 0xA)]
+    pub alignment: u8,
+    #[br(pad_before = 0x1)]
+    pub name_offset: u32,
+    #[br(pad_before = 0x2)]
+    pub first_block_offset: u16,
+    pub relocation_table_offset: u32,
+    pub file_size: u32,
+    #[br(magic = b"NX  ")]
+    pub texture_count: u32,
+    pub texture_info_array_offset: u64,
+}
+
+/// The info block of a single texture.
+#[binread]
+#[br(little, magic = b"BRTI")]
+#[derive(Debug)]
+pub(crate) struct TextureInfoBlock {
+    #[br(pad_before = 0xE)]
+    pub tile_mode: u16,
+    #[br(pad_before = 0x2)]
+    pub mip_count: u16,
+    #[br(pad_before = 0x4)]
+    pub format: u32,
+    #[br(pad_before = 0x4)]
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    pub array_count: u32,
+    pub block_height_log2: u32,
+    #[br(pad_before = 0x18)]
+    pub image_size: u32,
+    pub alignment: u32,
+    #[br(pad_before = 0x8)]
+    pub name_offset: u64,
+    #[br(pad_before = 0x8)]
+    pub mip_offsets_offset: u64,
+}
+
+impl TextureInfoBlock {
+    const IMAGE_SIZE_OFFSET: u64 = 0x50;
+    const SIZE: usize = 0xA0;
+}
+
+impl PtclFile {
+    /// Returns the textures stored in the texture array, in the order they are indexed by emitters.
+    ///
+    /// Resources without a texture array have no textures.
+    pub fn textures(&self) -> BinResult<Vec<PtclTexture>> {
+        match self.section(&Section::TEXTURE_ARRAY) {
+            Some(section) => read_textures(&section.data),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl PtclFile {
+    /// Replaces the image data of the texture at the given index in the texture array.
+    ///
+    /// Everything stored after the texture is moved to make room for the new data, and every offset pointing past it is updated.
+    /// The dimensions, format, and mipmap offsets of the texture are kept, so the new data must have the same layout.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::{PtclFile, PtclHeader, PtclTexture};
+    ///
+    /// let texture = PtclTexture {
+    ///     name: "smoke".to_string(),
+    ///     width: 4,
+    ///     height: 4,
+    ///     depth: 1,
+    ///     mip_count: 1,
+    ///     array_count: 1,
+    ///     format: 0x0B01,
+    ///     tile_mode: 0,
+    ///     block_height_log2: 0,
+    ///     mip_offsets: vec![0],
+    ///     data: vec![0xFF; 0x200],
+    /// };
+    /// let spark = PtclTexture {
+    ///     name: "spark".to_string(),
+    ///     ..texture.clone()
+    /// };
+    ///
+    /// let mut ptcl = PtclFile::new(PtclHeader::default());
+    /// ptcl.set_textures(&[texture, spark.clone()]);
+    /// ptcl.replace_texture(0, &[0x80; 0x400]).unwrap();
+    ///
+    /// let textures = ptcl.textures().unwrap();
+    ///
+    /// assert_eq!(textures[0].data, [0x80; 0x400]);
+    /// assert_eq!(textures[1], spark);
+    /// ```
+    pub fn replace_texture(&mut self, index: usize, data: &[u8]) -> BinResult<()> {
+        let section = self
+            .section_mut(&Section::TEXTURE_ARRAY)
+            .ok_or_else(|| invalid(0, "the resource has no texture array".to_string()))?;
+
+        section.data = replace_texture_data(&section.data, index, data)?;
+
+        Ok(())
+    }
+}
+
+impl PtclFile {
+    /// Replaces the texture array with a new container holding the given textures, in the order they are indexed by emitters.
+    ///
+    /// Fields of the texture info blocks which are not part of [`PtclTexture`] are written with the values of a plain 2D texture.
+    /// A texture array is added if the resource has none and there are textures to store.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::{PtclFile, PtclHeader, PtclTexture};
+    ///
+    /// let texture = PtclTexture {
+    ///     name: "smoke".to_string(),
+    ///     width: 4,
+    ///     height: 4,
+    ///     depth: 1,
+    ///     mip_count: 1,
+    ///     array_count: 1,
+    ///     format: 0x0B01,
+    ///     tile_mode: 0,
+    ///     block_height_log2: 0,
+    ///     mip_offsets: vec![0],
+    ///     data: vec![0xFF; 0x200],
+    /// };
+    ///
+    /// let mut ptcl = PtclFile::new(PtclHeader::default());
+    /// ptcl.set_textures(&[texture.clone()]);
+    ///
+    /// assert_eq!(ptcl.textures().unwrap(), [texture]);
+    /// ```
+    pub fn set_textures(&mut self, textures: &[PtclTexture]) {
+        let data = write_textures(textures);
+
+        match self.section_mut(&Section::TEXTURE_ARRAY) {
+            Some(section) => section.data = data,
+            None if !textures.is_empty() => {
+                let mut section = Section::new(Section::TEXTURE_ARRAY, data);
+
+                section.alignment = TEXTURE_ALIGNMENT as u32;
+                self.sections.push(section);
+            }
+            None => {}
+        }
+    }
+}
+
+/// Alignment of the image data of each texture written by [`PtclFile::set_textures`].
+const TEXTURE_ALIGNMENT: usize = 0x200;
+
+/// Size of the container header written by [`PtclFile::set_textures`].
+const CONTAINER_HEADER_SIZE: usize = 0x58;
+
+/// Writes a texture container holding the given textures.
+///
+/// Every pointer is listed in a single section of the relocation table, so the textures can be replaced afterwards.
+fn write_textures(textures: &[PtclTexture]) -> Vec<u8> {
+    let mut buffer = vec![0; CONTAINER_HEADER_SIZE];
+    let mut pointers = vec![0x28, 0x30];
+
+    buffer[..4].copy_from_slice(b"BNTX");
+    write_at(&mut buffer, 0x8, &0x40000u32.to_le_bytes());
+    write_at(&mut buffer, 0xC, &[0xFF, 0xFE]);
+    write_at(
+        &mut buffer,
+        0xE,
+        &[TEXTURE_ALIGNMENT.trailing_zeros() as u8, 0x40],
+    );
+    write_at(
+        &mut buffer,
+        0x16,
+        &(CONTAINER_HEADER_SIZE as u16).to_le_bytes(),
+    );
+    write_at(&mut buffer, 0x20, b"NX  ");
+    write_at(&mut buffer, 0x24, &(textures.len() as u32).to_le_bytes());
+
+    // The string block holds the empty name of the container followed by the name of each texture.
+    let string_block = buffer.len();
+
+    buffer.extend_from_slice(b"_STR");
+    buffer.resize(string_block + 0x10, 0);
+    buffer.extend_from_slice(&(textures.len() as u32 + 1).to_le_bytes());
+    buffer.resize(buffer.len() + 4, 0);
+
+    let mut name_offsets = Vec::new();
+
+    for name in std::iter::once("").chain(textures.iter().map(|texture| texture.name.as_str())) {
+        name_offsets.push(buffer.len() as u64);
+        buffer.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        buffer.extend_from_slice(name.as_bytes());
+        buffer.push(0);
+        buffer.resize(align(buffer.len(), 8), 0);
+    }
+
+    write_at(
+        &mut buffer,
+        0x10,
+        &(name_offsets[0] as u32 + 2).to_le_bytes(),
+    );
+
+    let string_size = (buffer.len() - string_block) as u32;
+    let info_array = buffer.len();
+
+    write_at(
+        &mut buffer,
+        string_block as u64 + 8,
+        &string_size.to_le_bytes(),
+    );
+    write_at(&mut buffer, 0x28, &(info_array as u64).to_le_bytes());
+    buffer.resize(info_array + 8 * textures.len(), 0);
+
+    let mut previous_block = string_block;
+    let mut mip_tables = Vec::new();
+
+    for (i, texture) in textures.iter().enumerate() {
+        let position = buffer.len();
+        let mip_table = position + TextureInfoBlock::SIZE;
+        let mip_count = texture.mip_count.max(1) as usize;
+
+        write_at(
+            &mut buffer,
+            (info_array + 8 * i) as u64,
+            &(position as u64).to_le_bytes(),
+        );
+        write_block_offsets(&mut buffer, previous_block, position);
+        buffer.resize(mip_table + 8 * mip_count, 0);
+        buffer[position..position + 4].copy_from_slice(b"BRTI");
+
+        for (offset, value) in [
+            (0x8, &(TextureInfoBlock::SIZE as u32).to_le_bytes()[..]),
+            (0x10, &[1, 2]),
+            (0x12, &texture.tile_mode.to_le_bytes()),
+            (0x16, &texture.mip_count.to_le_bytes()),
+            (0x18, &1u32.to_le_bytes()),
+            (0x1C, &texture.format.to_le_bytes()),
+            (0x20, &0x20u32.to_le_bytes()),
+            (0x24, &texture.width.to_le_bytes()),
+            (0x28, &texture.height.to_le_bytes()),
+            (0x2C, &texture.depth.to_le_bytes()),
+            (0x30, &texture.array_count.to_le_bytes()),
+            (0x34, &texture.block_height_log2.to_le_bytes()),
+            (0x50, &(texture.data.len() as u32).to_le_bytes()),
+            (0x54, &(TEXTURE_ALIGNMENT as u32).to_le_bytes()),
+            (0x58, &0x05040302u32.to_le_bytes()),
+            (0x5C, &1u32.to_le_bytes()),
+            (0x60, &name_offsets[i + 1].to_le_bytes()),
+            (0x68, &0x20u64.to_le_bytes()),
+            (0x70, &(mip_table as u64).to_le_bytes()),
+        ] {
+            write_at(&mut buffer, (position + offset) as u64, value);
+        }
+
+        pointers.extend([position + 0x60, position + 0x68, position + 0x70]);
+        pointers.extend((0..mip_count).map(|mip| mip_table + 8 * mip));
+        mip_tables.push(mip_table);
+        previous_block = position;
+    }
+
+    // Place the image data of the first texture right after the header of the data block.
+    let data_block = align(buffer.len() + 0x10, TEXTURE_ALIGNMENT) - 0x10;
+
+    buffer.resize(data_block, 0);
+    write_block_offsets(&mut buffer, previous_block, data_block);
+    write_at(&mut buffer, 0x30, &(data_block as u64).to_le_bytes());
+    buffer.extend_from_slice(b"BRTD");
+    buffer.resize(data_block + 0x10, 0);
+
+    for (texture, mip_table) in textures.iter().zip(mip_tables) {
+        let start = align(buffer.len(), TEXTURE_ALIGNMENT);
+
+        buffer.resize(start, 0);
+        buffer.extend_from_slice(&texture.data);
+
+        for mip in 0..texture.mip_count.max(1) as usize {
+            let offset = texture.mip_offsets.get(mip).copied().unwrap_or(0);
+
+            write_at(
+                &mut buffer,
+                (mip_table + 8 * mip) as u64,
+                &(start as u64 + offset).to_le_bytes(),
+            );
+        }
+    }
+
+    let relocation_table = align(buffer.len(), 8);
+
+    buffer.resize(relocation_table, 0);
+    write_at(
+        &mut buffer,
+        data_block as u64 + 8,
+        &((relocation_table - data_block) as u32).to_le_bytes(),
+    );
+    buffer.extend_from_slice(b"_RLT");
+    buffer.extend_from_slice(&(relocation_table as u32).to_le_bytes());
+    buffer.extend_from_slice(&1u32.to_le_bytes());
+    buffer.extend_from_slice(&0u32.to_le_bytes());
+    buffer.extend_from_slice(&0u64.to_le_bytes());
+    buffer.extend_from_slice(&0u32.to_le_bytes());
+    buffer.extend_from_slice(&(relocation_table as u32).to_le_bytes());
+    buffer.extend_from_slice(&0u32.to_le_bytes());
+    buffer.extend_from_slice(&(pointers.len() as u32).to_le_bytes());
+
+    for pointer in pointers {
+        buffer.extend_from_slice(&(pointer as u32).to_le_bytes());
+        buffer.extend_from_slice(&1u16.to_le_bytes());
+        buffer.extend_from_slice(&[1, 0]);
+    }
+
+    let file_size = buffer.len() as u32;
+
+    write_at(&mut buffer, 0x18, &(relocation_table as u32).to_le_bytes());
+    write_at(&mut buffer, 0x1C, &file_size.to_le_bytes());
+
+    buffer
+}
+
+/// Points the block at the given position at the next block, and sets its size if it was not set yet.
+fn write_block_offsets(buffer: &mut Vec<u8>, block: usize, next_block: usize) {
+    let next_offset = (next_block - block) as u32;
+
+    write_at(buffer, block as u64 + 4, &next_offset.to_le_bytes());
+
+    if buffer[block + 8..block + 12] == [0; 4] {
+        write_at(buffer, block as u64 + 8, &next_offset.to_le_bytes());
+    }
+}
+
+fn align(position: usize, alignment: usize) -> usize {
+    position.next_multiple_of(alignment)
+}
+
+/// Reads the offsets of every texture info block in the texture array data.
+pub(crate) fn texture_info_offsets(data: &[u8]) -> BinResult<Vec<u64>> {
+    let mut reader = Cursor::new(data);
+    let header = reader.read_le::<TextureContainerHeader>()?;
+
+    reader.seek(SeekFrom::Start(header.texture_info_array_offset))?;
+
+    (0..header.texture_count)
+        .map(|_| reader.read_le::<u64>())
+        .collect()
+}
+
+fn read_textures(data: &[u8]) -> BinResult<Vec<PtclTexture>> {
+    let mut reader = Cursor::new(data);
+
+    texture_info_offsets(data)?
+        .into_iter()
+        .map(|offset| {
+            reader.seek(SeekFrom::Start(offset))?;
+
+            let info = reader.read_le::<TextureInfoBlock>()?;
+
+            reader.seek(SeekFrom::Start(info.name_offset))?;
+
+            let name_length = reader.read_le::<u16>()? as usize;
+            let name_start = info.name_offset as usize + 2;
+            let name = data
+                .get(name_start..name_start + name_length)
+                .map(|name| String::from_utf8_lossy(name).into_owned())
+                .unwrap_or_default();
+
+            reader.seek(SeekFrom::Start(info.mip_offsets_offset))?;
+
+            let mip_offsets = (0..info.mip_count.max(1))
+                .map(|_| reader.read_le::<u64>())
+                .collect::<BinResult<Vec<_>>>()?;
+            let data_start = mip_offsets[0] as usize;
+            let data_end = data_start + info.image_size as usize;
+            let image_data =
+                data.get(data_start..data_end)
+                    .ok_or_else(|| binrw::Error::AssertFail {
+                        pos: info.mip_offsets_offset,
+                        message: format!("texture `{name}` data lies outside of the texture array"),
+                    })?;
+
+            Ok(PtclTexture {
+                name,
+                width: info.width,
+                height: info.height,
+                depth: info.depth,
+                mip_count: info.mip_count,
+                array_count: info.array_count,
+                format: info.format,
+                tile_mode: info.tile_mode,
+                block_height_log2: info.block_height_log2,
+                mip_offsets: mip_offsets
+                    .iter()
+                    .map(|offset| offset.saturating_sub(data_start as u64))
+                    .collect(),
+                data: image_data.to_vec(),
+            })
+        })
+        .collect()
+}
+
+fn replace_texture_data(bytes: &[u8], index: usize, data: &[u8]) -> BinResult<Vec<u8>> {
+    let mut reader = Cursor::new(bytes);
+    let header = reader.read_le::<TextureContainerHeader>()?;
+    let info_offsets = texture_info_offsets(bytes)?;
+    let info_offset = *info_offsets
+        .get(index)
+        .ok_or_else(|| invalid(0, format!("texture index {index} is out of range")))?;
+    let mut alignment = 1 << header.alignment.min(31);
+    let mut info = None;
+
+    for offset in &info_offsets {
+        reader.seek(SeekFrom::Start(*offset))?;
+
+        let texture_info = reader.read_le::<TextureInfoBlock>()?;
+
+        alignment = alignment.max(texture_info.alignment as i64);
+
+        if *offset == info_offset {
+            info = Some(texture_info);
+        }
+    }
+
+    let info = info.unwrap();
+
+    reader.seek(SeekFrom::Start(info.mip_offsets_offset))?;
+
+    let start = reader.read_le::<u64>()?;
+    let end = start + info.image_size as u64;
+
+    if end > bytes.len() as u64 {
+        return Err(invalid(
+            info.mip_offsets_offset,
+            format!("texture {index} data lies outside of the texture array"),
+        ));
+    }
+
+    // Keep everything after the texture at the same alignment by moving it in whole alignment steps.
+    let difference = data.len() as i64 - info.image_size as i64;
+    let delta = (difference + alignment - 1).div_euclid(alignment) * alignment;
+    let shift = |offset: u64| {
+        if offset >= end {
+            (offset as i64 + delta) as u64
+        } else {
+            offset
+        }
+    };
+    let mut buffer = bytes.to_vec();
+
+    write_at(
+        &mut buffer,
+        info_offset + TextureInfoBlock::IMAGE_SIZE_OFFSET,
+        &(data.len() as u32).to_le_bytes(),
+    );
+
+    for (offset, value) in [
+        (0x10, header.name_offset),
+        (0x18, header.relocation_table_offset),
+        (0x1C, header.file_size),
+    ] {
+        write_at(
+            &mut buffer,
+            offset,
+            &(shift(value as u64) as u32).to_le_bytes(),
+        );
+    }
+
+    relocate_blocks(
+        &mut reader,
+        &mut buffer,
+        header.first_block_offset as u64,
+        start,
+        end,
+        delta,
+    )?;
+    relocate_pointers(
+        &mut reader,
+        &mut buffer,
+        header.relocation_table_offset as u64,
+        start,
+        end,
+        delta,
+        shift,
+    )?;
+
+    let region_size = (info.image_size as i64 + delta) as usize;
+    let mut replaced = Vec::with_capacity((buffer.len() as i64 + delta) as usize);
+
+    replaced.extend_from_slice(&buffer[..start as usize]);
+    replaced.extend_from_slice(data);
+    replaced.resize(start as usize + region_size, 0);
+    replaced.extend_from_slice(&buffer[end as usize..]);
+
+    Ok(replaced)
+}
+
+/// Resizes the block containing the texture data and updates the offsets to the blocks after it.
+fn relocate_blocks(
+    reader: &mut Cursor<&[u8]>,
+    buffer: &mut Vec<u8>,
+    first_block_offset: u64,
+    start: u64,
+    end: u64,
+    delta: i64,
+) -> BinResult<()> {
+    let mut position = first_block_offset;
+
+    while position + 0x10 <= buffer.len() as u64 {
+        reader.seek(SeekFrom::Start(position + 4))?;
+
+        let next_offset = reader.read_le::<u32>()? as u64;
+        let size = reader.read_le::<u32>()? as u64;
+
+        if position <= start && position + size >= end {
+            write_at(
+                buffer,
+                position + 8,
+                &((size as i64 + delta) as u32).to_le_bytes(),
+            );
+        }
+
+        if next_offset == 0 {
+            break;
+        }
+
+        if position < end && position + next_offset >= end {
+            write_at(
+                buffer,
+                position + 4,
+                &((next_offset as i64 + delta) as u32).to_le_bytes(),
+            );
+        }
+
+        position += next_offset;
+    }
+
+    Ok(())
+}
+
+/// Updates the position of the relocation table, every pointer listed in it, and the regions it covers.
+fn relocate_pointers<F: Fn(u64) -> u64>(
+    reader: &mut Cursor<&[u8]>,
+    buffer: &mut Vec<u8>,
+    relocation_table_offset: u64,
+    start: u64,
+    end: u64,
+    delta: i64,
+    shift: F,
+) -> BinResult<()> {
+    reader.seek(SeekFrom::Start(relocation_table_offset))?;
+
+    let table = reader.read_le::<RelocationTableHeader>()?;
+
+    write_at(
+        buffer,
+        relocation_table_offset + 4,
+        &(shift(table.position as u64) as u32).to_le_bytes(),
+    );
+
+    let sections_offset = relocation_table_offset + 0x10;
+    let entries_offset = sections_offset + table.section_count as u64 * 0x18;
+    let mut entry_count = 0;
+
+    for i in 0..table.section_count as u64 {
+        reader.seek(SeekFrom::Start(sections_offset + i * 0x18 + 8))?;
+
+        let position = reader.read_le::<u32>()? as u64;
+        let size = reader.read_le::<u32>()? as u64;
+        let entry_index = reader.read_le::<u32>()?;
+        let section_entry_count = reader.read_le::<u32>()?;
+
+        entry_count = entry_count.max(entry_index + section_entry_count);
+
+        if position >= end {
+            write_at(
+                buffer,
+                sections_offset + i * 0x18 + 8,
+                &(shift(position) as u32).to_le_bytes(),
+            );
+        } else if position <= start && position + size >= end {
+            write_at(
+                buffer,
+                sections_offset + i * 0x18 + 0xC,
+                &((size as i64 + delta) as u32).to_le_bytes(),
+            );
+        }
+    }
+
+    for i in 0..entry_count as u64 {
+        reader.seek(SeekFrom::Start(entries_offset + i * 8))?;
+
+        let entry = reader.read_le::<RelocationEntry>()?;
+        let mut position = entry.position as u64;
+
+        for _ in 0..entry.struct_count {
+            for _ in 0..entry.offset_count {
+                reader.seek(SeekFrom::Start(position))?;
+
+                let pointer = reader.read_le::<u64>()?;
+
+                write_at(buffer, position, &shift(pointer).to_le_bytes());
+                position += 8;
+            }
+
+            position += entry.padding_count as u64 * 8;
+        }
+    }
+
+    Ok(())
+}
+
+/// The header of the relocation table of the texture array data.
+#[binread]
+#[br(little, magic = b"_RLT")]
+#[derive(Debug)]
+struct RelocationTableHeader {
+    position: u32,
+    section_count: u32,
+}
+
+/// A run of pointers listed in the relocation table.
+#[binread]
+#[br(little)]
+#[derive(Debug)]
+struct RelocationEntry {
+    position: u32,
+    struct_count: u16,
+    offset_count: u8,
+    padding_count: u8,
+}
+
+fn invalid(pos: u64, message: String) -> binrw::Error {
+    binrw::Error::AssertFail { pos, message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texture(name: &str, size: usize, value: u8) -> PtclTexture {
+        PtclTexture {
+            name: name.to_string(),
+            width: 16,
+            height: 16,
+            depth: 1,
+            mip_count: 2,
+            array_count: 1,
+            format: 0x0B01,
+            tile_mode: 0,
+            block_height_log2: 0,
+            mip_offsets: vec![0, 0x40],
+            data: vec![value; size],
+        }
+    }
+
+    fn read_u32(data: &[u8], offset: usize) -> usize {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+    }
+
+    #[test]
+    fn replace_first_texture() {
+        let textures = [
+            texture("smoke", 0x300, 1),
+            texture("spark", 0x80, 2),
+            texture("flash", 0x400, 3),
+        ];
+        let bytes = write_textures(&textures);
+
+        for size in [0x500, 0x100, 0x300] {
+            let data = vec![0xFF; size];
+            let replaced = replace_texture_data(&bytes, 0, &data).unwrap();
+
+            let mut expected = textures.clone();
+            expected[0].data = data.clone();
+
+            assert_eq!(read_textures(&replaced).unwrap(), expected);
+
+            // A container written from scratch places every block and texture at the same aligned positions,
+            // so its pointers, block sizes, and relocation table must match exactly.
+            let written = write_textures(&expected);
+
+            assert_eq!(replaced, written);
+
+            let relocation_table = read_u32(&replaced, 0x18);
+            let data_block = u64::from_le_bytes(replaced[0x30..0x38].try_into().unwrap()) as usize;
+
+            assert_eq!(&replaced[relocation_table..relocation_table + 4], b"_RLT");
+            assert_eq!(&replaced[data_block..data_block + 4], b"BRTD");
+            assert_eq!(
+                read_u32(&replaced, data_block + 8),
+                relocation_table - data_block
+            );
+            assert_eq!(read_u32(&replaced, 0x1C), replaced.len());
+        }
+    }
+}
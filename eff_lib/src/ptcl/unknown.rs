@@ -0,0 +1,92 @@
+use crate::{PtclFile, Section};
+
+/// A section of a [`PtclFile`] which is not otherwise understood, and is written back unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownSection<'a> {
+    /// Four-character identifier of the section.
+    pub id: [u8; 4],
+
+    /// Offset the section was read at, or `None` if it was not read from a resource.
+    pub offset: Option<u64>,
+
+    /// Binary data of the section.
+    pub bytes: &'a [u8],
+}
+
+impl Section {
+    /// Returns `true` if the identifier of the section is one understood by [`PtclFile`], and `false` otherwise.
+    pub fn is_known(&self) -> bool {
+        [
+            Self::EMITTER_SET_ARRAY,
+            Self::EMITTER_SET,
+            Self::EMITTER,
+            Self::TEXTURE_ARRAY,
+            Self::SHADER_ARRAY,
+            Self::COMPUTE_SHADER_ARRAY,
+            Self::PRIMITIVE_ARRAY,
+            Self::PRIMITIVE,
+            Self::GRAVITY_FIELD,
+            Self::TURBULENCE_FIELD,
+            Self::MAGNET_FIELD,
+        ]
+        .contains(&self.magic)
+    }
+}
+
+impl PtclFile {
+    /// Returns every section which is not otherwise understood, with each section before its children.
+    ///
+    /// Sections below the shader arrays are exposed through [`PtclFile::shaders`], so they are not included.
+    /// Unknown sections are kept as-is, so a resource which is read and written without changes is identical to the original.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::{PtclFile, PtclHeader, Section};
+    ///
+    /// let mut ptcl = PtclFile::new(PtclHeader::default());
+    /// ptcl.sections.push(Section::new(*b"G3PR", vec![1, 2, 3]));
+    ///
+    /// let bytes = ptcl.to_bytes().unwrap();
+    /// let ptcl = PtclFile::from_bytes(&bytes).unwrap();
+    /// let unknown_sections = ptcl.unknown_sections();
+    ///
+    /// assert_eq!(unknown_sections.len(), 1);
+    /// assert_eq!(&unknown_sections[0].id, b"G3PR");
+    /// assert_eq!(unknown_sections[0].offset, Some(0x20));
+    /// assert_eq!(unknown_sections[0].bytes, [1, 2, 3]);
+    /// assert_eq!(ptcl.to_bytes().unwrap(), bytes);
+    /// ```
+    pub fn unknown_sections(&self) -> Vec<UnknownSection<'_>> {
+        let mut unknown_sections = Vec::new();
+
+        for section in &self.sections {
+            collect_unknown_sections(section, &mut unknown_sections);
+        }
+
+        unknown_sections
+    }
+}
+
+fn collect_unknown_sections<'a>(
+    section: &'a Section,
+    unknown_sections: &mut Vec<UnknownSection<'a>>,
+) {
+    if section.magic == Section::SHADER_ARRAY || section.magic == Section::COMPUTE_SHADER_ARRAY {
+        return;
+    }
+
+    if !section.is_known() {
+        unknown_sections.push(UnknownSection {
+            id: section.magic,
+            offset: section.layout.map(|layout| layout.position),
+            bytes: &section.data,
+        });
+    }
+
+    for child in &section.children {
+        collect_unknown_sections(child, unknown_sections);
+    }
+}
@@ -99,6 +99,22 @@ impl CString {
     pub fn to_string(&self) -> Result<String, str::Utf8Error> {
         self.to_str().map(|s| s.to_string())
     }
+
+    /// Converts the contained string to a [`String`], replacing invalid UTF-8 sequences with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// use eff_lib::CString;
+    ///
+    /// let s = CString::from_bytes(b"bullet\xFFB1\0");
+    /// assert_eq!(s.to_string_lossy(), "bullet\u{FFFD}B1".to_string());
+    /// ```
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.0).into_owned()
+    }
 }
 
 impl FromStr for CString {
@@ -0,0 +1,337 @@
+use core::{fmt, str};
+
+/// An error that can occur when parsing an [`EffView`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ViewError {
+    /// The buffer does not start with the EFF magic.
+    InvalidMagic,
+
+    /// A table count in the header is negative.
+    InvalidCount,
+
+    /// The buffer ended before the named table.
+    UnexpectedEof(&'static str),
+}
+
+impl fmt::Display for ViewError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMagic => write!(f, "invalid magic, expected `EFFN`"),
+            Self::InvalidCount => write!(f, "negative table count in header"),
+            Self::UnexpectedEof(table) => write!(f, "unexpected end of buffer in {table}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ViewError {}
+
+/// A borrowed, read-only view over the bytes of an EFF file.
+///
+/// Unlike [`EffFile`](crate::EffFile), parsing a view neither allocates nor copies the buffer, and is available without the `std` feature.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use eff_lib::EffView;
+///
+/// let bytes = [
+///     b'E', b'F', b'F', b'N', 0, 0, 2, 0, // magic, version
+///     1, 0, 0, 0, 0, 0, 0xFF, 0xFF, // counts, resource alignment factor
+///     0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, // effect handle
+///     b'S', b'M', b'O', b'K', b'E', 0, // effect handle name
+/// ];
+/// let view = EffView::parse(&bytes).unwrap();
+///
+/// assert_eq!(view.effect_handle_count(), 1);
+/// assert_eq!(view.effect_handle(0).unwrap().emitter_set_handle(), 1);
+/// assert_eq!(view.effect_handle_names().get_str(0), Some("SMOKE"));
+/// assert!(view.resource_data().is_none());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EffView<'a> {
+    effect_handles: &'a [u8],
+    effect_group_elements: &'a [u8],
+    effect_model_entries: &'a [u8],
+    effect_handle_names: NameTable<'a>,
+    effect_model_names: NameTable<'a>,
+    parent_joint_names: NameTable<'a>,
+    resource_data: Option<&'a [u8]>,
+}
+
+impl<'a> EffView<'a> {
+    const MAGIC: &'static [u8; 4] = b"EFFN";
+    const HEADER_SIZE: usize = 0x10;
+    const EFFECT_HANDLE_SIZE: usize = 0x10;
+    const EFFECT_GROUP_ELEMENT_SIZE: usize = 0x4;
+    const EFFECT_MODEL_ENTRY_SIZE: usize = 0x1;
+    const RESOURCE_ALIGNMENT_COEFFICIENT: usize = 0x1000;
+
+    /// Parses the given buffer, borrowing every table from it.
+    pub fn parse(data: &'a [u8]) -> Result<Self, ViewError> {
+        if data.get(..4) != Some(Self::MAGIC.as_slice()) {
+            return Err(ViewError::InvalidMagic);
+        }
+
+        let header = data
+            .get(..Self::HEADER_SIZE)
+            .ok_or(ViewError::UnexpectedEof("header"))?;
+        let count = |offset: usize| -> Result<usize, ViewError> {
+            usize::try_from(read_i16(header, offset)).map_err(|_| ViewError::InvalidCount)
+        };
+        let effect_handle_count = count(0x8)?;
+        let effect_model_count = count(0xA)?;
+        let effect_group_element_count = count(0xC)?;
+        let resource_alignment_factor = read_i16(header, 0xE);
+
+        let mut offset = Self::HEADER_SIZE;
+        let mut table = |size: usize, name: &'static str| -> Result<&'a [u8], ViewError> {
+            let table = data
+                .get(offset..offset + size)
+                .ok_or(ViewError::UnexpectedEof(name))?;
+
+            offset += size;
+
+            Ok(table)
+        };
+        let effect_handles = table(
+            effect_handle_count * Self::EFFECT_HANDLE_SIZE,
+            "effect handles",
+        )?;
+        let effect_group_elements = table(
+            effect_group_element_count * Self::EFFECT_GROUP_ELEMENT_SIZE,
+            "effect group elements",
+        )?;
+        let effect_model_entries = table(
+            effect_model_count * Self::EFFECT_MODEL_ENTRY_SIZE,
+            "effect model entries",
+        )?;
+
+        let (effect_handle_names, offset) =
+            NameTable::parse(data, offset, effect_handle_count, "effect handle names")?;
+        let (effect_model_names, offset) =
+            NameTable::parse(data, offset, effect_model_count, "effect model names")?;
+        let (parent_joint_names, offset) = NameTable::parse(
+            data,
+            offset,
+            effect_group_element_count,
+            "parent joint names",
+        )?;
+
+        let resource_data = (resource_alignment_factor != -1).then(|| {
+            let alignment = if resource_alignment_factor < 1 {
+                1
+            } else {
+                resource_alignment_factor as usize * Self::RESOURCE_ALIGNMENT_COEFFICIENT
+            };
+
+            &data[offset.next_multiple_of(alignment).min(data.len())..]
+        });
+
+        Ok(Self {
+            effect_handles,
+            effect_group_elements,
+            effect_model_entries,
+            effect_handle_names,
+            effect_model_names,
+            parent_joint_names,
+            resource_data,
+        })
+    }
+
+    /// Returns the number of effect handles.
+    pub fn effect_handle_count(&self) -> usize {
+        self.effect_handles.len() / Self::EFFECT_HANDLE_SIZE
+    }
+
+    /// Returns the effect handle at the given index, if it exists.
+    pub fn effect_handle(&self, index: usize) -> Option<EffectHandleView<'a>> {
+        let start = index.checked_mul(Self::EFFECT_HANDLE_SIZE)?;
+
+        self.effect_handles
+            .get(start..start + Self::EFFECT_HANDLE_SIZE)
+            .map(EffectHandleView)
+    }
+
+    /// Returns an iterator over the effect handles.
+    pub fn effect_handles(&self) -> impl Iterator<Item = EffectHandleView<'a>> {
+        self.effect_handles
+            .chunks_exact(Self::EFFECT_HANDLE_SIZE)
+            .map(EffectHandleView)
+    }
+
+    /// Returns the number of effect group elements.
+    pub fn effect_group_element_count(&self) -> usize {
+        self.effect_group_elements.len() / Self::EFFECT_GROUP_ELEMENT_SIZE
+    }
+
+    /// Returns the effect group element at the given index, if it exists.
+    pub fn effect_group_element(&self, index: usize) -> Option<EffectGroupElementView<'a>> {
+        let start = index.checked_mul(Self::EFFECT_GROUP_ELEMENT_SIZE)?;
+
+        self.effect_group_elements
+            .get(start..start + Self::EFFECT_GROUP_ELEMENT_SIZE)
+            .map(EffectGroupElementView)
+    }
+
+    /// Returns an iterator over the effect group elements.
+    pub fn effect_group_elements(&self) -> impl Iterator<Item = EffectGroupElementView<'a>> {
+        self.effect_group_elements
+            .chunks_exact(Self::EFFECT_GROUP_ELEMENT_SIZE)
+            .map(EffectGroupElementView)
+    }
+
+    /// Returns the number of effect model entries.
+    pub fn effect_model_count(&self) -> usize {
+        self.effect_model_entries.len() / Self::EFFECT_MODEL_ENTRY_SIZE
+    }
+
+    /// Returns the unknown value of the effect model entry at the given index, if it exists.
+    pub fn effect_model_entry(&self, index: usize) -> Option<i8> {
+        self.effect_model_entries.get(index).map(|b| *b as i8)
+    }
+
+    /// Returns the table of effect handle names.
+    pub fn effect_handle_names(&self) -> NameTable<'a> {
+        self.effect_handle_names
+    }
+
+    /// Returns the table of effect model names.
+    pub fn effect_model_names(&self) -> NameTable<'a> {
+        self.effect_model_names
+    }
+
+    /// Returns the table of parent joint names to emitter sets in effect group elements.
+    pub fn parent_joint_names(&self) -> NameTable<'a> {
+        self.parent_joint_names
+    }
+
+    /// Returns the data buffer for the contained file resource, if it exists.
+    pub fn resource_data(&self) -> Option<&'a [u8]> {
+        self.resource_data
+    }
+}
+
+/// A borrowed view over the data associated with an effect handle.
+#[derive(Debug, Clone, Copy)]
+pub struct EffectHandleView<'a>(&'a [u8]);
+
+impl EffectHandleView<'_> {
+    /// Returns the bits of the flags representing the attributes of an effect.
+    pub fn flags(&self) -> u32 {
+        u32::from_le_bytes([self.0[0], self.0[1], self.0[2], self.0[3]])
+    }
+
+    /// Returns the positive index to the emitter set.
+    pub fn emitter_set_handle(&self) -> i32 {
+        read_i32(self.0, 0x4)
+    }
+
+    /// Returns the positive index to the effect model entry.
+    pub fn effect_model_entry_handle(&self) -> i32 {
+        read_i32(self.0, 0x8)
+    }
+
+    /// Returns the positive index to the first element in the effect group.
+    pub fn effect_group_element_start(&self) -> i16 {
+        read_i16(self.0, 0xC)
+    }
+
+    /// Returns the number of elements in the effect group.
+    pub fn effect_group_element_count(&self) -> i16 {
+        read_i16(self.0, 0xE)
+    }
+}
+
+/// A borrowed view over the data associated with an effect group element.
+#[derive(Debug, Clone, Copy)]
+pub struct EffectGroupElementView<'a>(&'a [u8]);
+
+impl EffectGroupElementView<'_> {
+    /// Returns the frame to request the emitter set on.
+    pub fn emitter_set_start_frame(&self) -> i16 {
+        read_i16(self.0, 0x0)
+    }
+
+    /// Returns the positive index to the emitter set.
+    pub fn emitter_set_handle(&self) -> i16 {
+        read_i16(self.0, 0x2)
+    }
+}
+
+/// A borrowed table of nul-terminated strings.
+#[derive(Debug, Clone, Copy)]
+pub struct NameTable<'a> {
+    data: &'a [u8],
+    count: usize,
+}
+
+impl<'a> NameTable<'a> {
+    fn parse(
+        data: &'a [u8],
+        offset: usize,
+        count: usize,
+        name: &'static str,
+    ) -> Result<(Self, usize), ViewError> {
+        let mut end = offset;
+
+        for _ in 0..count {
+            let len = data
+                .get(end..)
+                .and_then(|rest| rest.iter().position(|b| *b == 0))
+                .ok_or(ViewError::UnexpectedEof(name))?;
+
+            end += len + 1;
+        }
+
+        Ok((
+            Self {
+                data: &data[offset..end],
+                count,
+            },
+            end,
+        ))
+    }
+
+    /// Returns the number of names in the table.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if the table contains no names, and `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the bytes of the name at the given index, without the nul terminator.
+    pub fn get(&self, index: usize) -> Option<&'a [u8]> {
+        self.iter().nth(index)
+    }
+
+    /// Returns the name at the given index if it exists and contains valid UTF-8.
+    pub fn get_str(&self, index: usize) -> Option<&'a str> {
+        self.get(index).and_then(|name| str::from_utf8(name).ok())
+    }
+
+    /// Returns an iterator over the bytes of each name, without the nul terminators.
+    pub fn iter(&self) -> impl Iterator<Item = &'a [u8]> {
+        let data = self.data;
+
+        data.split(|b| *b == 0).take(self.count)
+    }
+}
+
+fn read_i16(data: &[u8], offset: usize) -> i16 {
+    i16::from_le_bytes([data[offset], data[offset + 1]])
+}
+
+fn read_i32(data: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}